@@ -0,0 +1,33 @@
+use crate::data::time::Date;
+use serde::{Deserialize, Serialize};
+
+/// Why did a [`Character`](crate::data::character::Character) die?
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum DeathCause {
+    /// The [`Character`](crate::data::character::Character) outgrew its last [`LifeStage`](crate::data::character::race::stage::LifeStage).
+    OldAge,
+    Disease,
+    Accident,
+    Violence,
+}
+
+/// When & why a [`Character`](crate::data::character::Character) died.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Death {
+    date: Date,
+    cause: DeathCause,
+}
+
+impl Death {
+    pub fn new(date: Date, cause: DeathCause) -> Self {
+        Self { date, cause }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn cause(&self) -> DeathCause {
+        self.cause
+    }
+}
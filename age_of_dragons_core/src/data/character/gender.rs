@@ -1,7 +1,21 @@
+use serde::{Deserialize, Serialize};
+
 /// The gender of the [`Character`](crate::data::character::Character).
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Gender {
     Genderless,
     Female,
     Male,
+    /// Is both female & male, so it can mate with other hermaphrodites & give birth.
+    Hermaphrodite,
+}
+
+impl Gender {
+    /// All genders in a fixed order.
+    pub const ALL: [Gender; 4] = [
+        Gender::Genderless,
+        Gender::Female,
+        Gender::Male,
+        Gender::Hermaphrodite,
+    ];
 }
@@ -1,32 +1,31 @@
+use crate::data::character::race::manager::RaceMgr;
 use crate::data::character::{Character, CharacterId};
-use anyhow::Result;
+use crate::data::storage::Storage;
+use anyhow::{bail, Context, Result};
 
 /// Stores all the [`Character`]s.
-#[derive(Default, Debug)]
-pub struct CharacterMgr {
-    characters: Vec<Character>,
-}
+pub type CharacterMgr = Storage<CharacterId, Character>;
 
 impl CharacterMgr {
-    /// Uses the function *f* to create a [`Character`] with the next [`CharacterId`].
-    pub fn create<F>(&mut self, f: F) -> Result<CharacterId>
-    where
-        F: FnOnce(CharacterId) -> Result<Character>,
-    {
-        let id = CharacterId::new(self.characters.len());
-        self.characters.push(f(id)?);
-        Ok(id)
-    }
+    /// Validates all [`Character`]s against their [`Race`](crate::data::character::race::Race),
+    /// which can't be done while deserializing them.
+    /// Living characters must also be active, because archived ones aren't simulated.
+    pub fn validate(&self, race_manager: &RaceMgr) -> Result<()> {
+        for (index, character) in self.get_all().iter().enumerate() {
+            let id = character.id();
 
-    pub fn get_all(&self) -> &Vec<Character> {
-        &self.characters
-    }
+            if id.id() != index {
+                bail!("Character {} is stored at index {}!", id.id(), index);
+            } else if character.is_alive() && self.is_archived(id) {
+                bail!("Character {} is alive, but archived!", id.id());
+            }
 
-    pub fn get(&self, id: CharacterId) -> Option<&Character> {
-        self.characters.get(id.0)
-    }
+            let race = race_manager
+                .get(character.race_id())
+                .with_context(|| format!("Character {} has an unknown race!", id.id()))?;
+            character.validate(race)?;
+        }
 
-    pub fn get_mut(&mut self, id: CharacterId) -> Option<&mut Character> {
-        self.characters.get_mut(id.0)
+        Ok(())
     }
 }
@@ -1,17 +1,21 @@
+use crate::data::character::death::{Death, DeathCause};
 use crate::data::character::gender::Gender;
 use crate::data::character::race::stage::LifeStageId;
 use crate::data::character::race::{Race, RaceId};
 use crate::data::name::Name;
+use crate::data::storage::StorageId;
 use crate::data::time::{Date, Duration};
 use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
 
+pub mod death;
 pub mod gender;
 pub mod manager;
 pub mod race;
 pub mod relation;
 
 /// The id of a [`Character`].
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct CharacterId(usize);
 
 impl CharacterId {
@@ -24,17 +28,31 @@ impl CharacterId {
     }
 }
 
+impl StorageId for CharacterId {
+    fn new(id: usize) -> Self {
+        Self(id)
+    }
+
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
 /// A character is an entity important enough to be fully simulated.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Character {
     id: CharacterId,
     name: Name,
+    /// The surname depends on the [`NamingRule`](race::naming::NamingRule) of the race.
+    surname: Option<Name>,
     race_id: RaceId,
     life_stage: LifeStageId,
     gender: Gender,
     birth_date: Date,
-    /// The death date is only available, if the character is death.
-    death_date: Option<Date>,
+    /// The death is only available, if the character is dead.
+    death: Option<Death>,
+    /// When the character last gave birth, which starts the cooldown of its reproduction.
+    last_birth: Option<Date>,
 }
 
 impl Character {
@@ -59,37 +77,65 @@ impl Character {
         race: &Race,
         gender: Gender,
         birth_date: Date,
-        death_date: Option<Date>,
+        death: Option<Death>,
     ) -> Result<Self> {
-        let name = name.into();
+        let name = Name::new(name).with_context(|| format!("Failed to create character {}", id))?;
+        let character = Self {
+            id: CharacterId::new(id),
+            name,
+            surname: None,
+            gender,
+            race_id: race.id(),
+            life_stage: LifeStageId::new(0),
+            birth_date,
+            death,
+            last_birth: None,
+        };
 
-        if !race.gender_option().is_valid(gender) {
+        character.validate(race)?;
+
+        Ok(character)
+    }
+
+    /// Validates the character against its [`Race`], which is also needed after loading it.
+    pub fn validate(&self, race: &Race) -> Result<()> {
+        if race.id() != self.race_id {
+            bail!(
+                "Character {} is invalid, because its race {:?} isn't {:?}!",
+                self.id.0,
+                self.race_id,
+                race.id()
+            );
+        } else if !race.gender_option().is_valid(self.gender) {
             bail!(
                 "Character {} is invalid, because {:?} doesn't match the race's {:?}!",
-                id,
-                gender,
+                self.id.0,
+                self.gender,
                 race.gender_option()
             );
-        } else if death_date.map(|d| d < birth_date).unwrap_or(false) {
+        } else if self.life_stage.index() >= race.stages().len() {
+            bail!(
+                "Character {} is invalid, because its race has no life stage {}!",
+                self.id.0,
+                self.life_stage.index()
+            );
+        } else if let Some(death) = self.death.filter(|d| d.date() < self.birth_date) {
             bail!(
                 "The character {}'s death {:?} happened before  its birth {:?}!",
-                id,
-                death_date.unwrap(),
-                birth_date
+                self.id.0,
+                death.date(),
+                self.birth_date
+            );
+        } else if let Some(last_birth) = self.last_birth.filter(|&date| date < self.birth_date) {
+            bail!(
+                "The character {} gave birth at {:?} before its own birth {:?}!",
+                self.id.0,
+                last_birth,
+                self.birth_date
             );
         }
 
-        let name = Name::new(name).with_context(|| format!("Failed to create character {}", id))?;
-
-        Ok(Self {
-            id: CharacterId::new(id),
-            name,
-            gender,
-            race_id: race.id(),
-            life_stage: LifeStageId::new(0),
-            birth_date,
-            death_date,
-        })
+        Ok(())
     }
 
     /// A simple way to create a character for testing.
@@ -98,16 +144,18 @@ impl Character {
         race_id: RaceId,
         gender: Gender,
         birth_date: Date,
-        death_date: Option<Date>,
+        death: Option<Death>,
     ) -> Self {
         Self {
             id: CharacterId::new(id),
             name: Name::new(format!("Chacarer {}", id)).unwrap(),
+            surname: None,
             gender,
             race_id,
             life_stage: LifeStageId::new(0),
             birth_date,
-            death_date,
+            death,
+            last_birth: None,
         }
     }
 
@@ -119,6 +167,35 @@ impl Character {
         &self.name
     }
 
+    pub fn surname(&self) -> Option<&Name> {
+        self.surname.as_ref()
+    }
+
+    pub fn set_surname(&mut self, surname: Option<Name>) {
+        self.surname = surname;
+    }
+
+    /// Returns the name & the surname, if available:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::Character;
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    ///# use age_of_dragons_core::data::character::race::RaceId;
+    ///# use age_of_dragons_core::data::name::Name;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let mut character = Character::simple(3, RaceId::new(0), Male, Date::new(0), None);
+    /// assert_eq!(character.full_name(), "Chacarer 3");
+    ///
+    /// character.set_surname(Some(Name::new("Ironwing").unwrap()));
+    /// assert_eq!(character.full_name(), "Chacarer 3 Ironwing");
+    /// ```
+    pub fn full_name(&self) -> String {
+        match &self.surname {
+            Some(surname) => format!("{} {}", self.name.to_str(), surname.to_str()),
+            None => self.name.to_str().to_string(),
+        }
+    }
+
     pub fn race_id(&self) -> RaceId {
         self.race_id
     }
@@ -135,29 +212,45 @@ impl Character {
         self.gender
     }
 
+    pub fn set_gender(&mut self, gender: Gender) {
+        self.gender = gender;
+    }
+
     pub fn birth_date(&self) -> Date {
         self.birth_date
     }
 
+    pub fn death(&self) -> Option<Death> {
+        self.death
+    }
+
     pub fn death_date(&self) -> Option<Date> {
-        self.death_date
+        self.death.map(|death| death.date())
+    }
+
+    pub fn set_death(&mut self, date: Date, cause: DeathCause) {
+        self.death = Some(Death::new(date, cause));
     }
 
-    pub fn set_death_date(&mut self, date: Date) {
-        self.death_date = Some(date);
+    pub fn last_birth(&self) -> Option<Date> {
+        self.last_birth
+    }
+
+    pub fn set_last_birth(&mut self, date: Date) {
+        self.last_birth = Some(date);
     }
 
     pub fn is_alive(&self) -> bool {
-        self.death_date.is_none()
+        self.death.is_none()
     }
 
     pub fn is_dead(&self) -> bool {
-        self.death_date.is_some()
+        self.death.is_some()
     }
 
     /// Calculates the current age of an alive character or the age they reached before dying otherwise.
     pub fn calculate_age(&self, now: Date) -> Duration {
-        if let Some(death_date) = self.death_date {
+        if let Some(death_date) = self.death_date() {
             death_date
         } else if now < self.birth_date {
             panic!("Failed to calculate age before birth!")
@@ -185,9 +278,23 @@ mod tests {
     fn test_new_with_death_before_birth() {
         let race = Race::simple(32, TwoGenders);
 
-        assert!(
-            Character::new(0, "C0", &race, Female, Date::new(20), Some(Date::new(10))).is_err()
-        );
+        assert!(Character::new(0, "C0", &race, Female, Date::new(20), Some(death(10))).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let race = Race::simple(32, TwoGenders);
+        let mut character = Character::new(0, "C0", &race, Female, Date::new(20), None).unwrap();
+
+        assert!(character.validate(&race).is_ok());
+        assert!(character.validate(&Race::simple(33, TwoGenders)).is_err());
+
+        character.set_life_stage(LifeStageId::new(1));
+        assert!(character.validate(&race).is_err());
+
+        character.set_life_stage(LifeStageId::new(0));
+        character.set_last_birth(Date::new(19));
+        assert!(character.validate(&race).is_err());
     }
 
     #[test]
@@ -206,10 +313,11 @@ mod tests {
     #[test]
     fn test_dead_character() {
         let race = Race::simple(32, TwoGenders);
-        let date = Date::new(45);
-        let character = Character::new(0, "C0", &race, Female, Date::new(20), Some(date)).unwrap();
+        let death = death(45);
+        let character = Character::new(0, "C0", &race, Female, Date::new(20), Some(death)).unwrap();
 
-        assert_eq!(character.death_date(), Some(date));
+        assert_eq!(character.death(), Some(death));
+        assert_eq!(character.death_date(), Some(Date::new(45)));
         assert!(!character.is_alive());
         assert!(character.is_dead());
 
@@ -228,4 +336,8 @@ mod tests {
 
         character.calculate_age(Date::new(10));
     }
+
+    fn death(year: u32) -> Death {
+        Death::new(Date::new(year), DeathCause::Disease)
+    }
 }
@@ -0,0 +1,159 @@
+use crate::data::character::gender::Gender;
+use crate::data::probability::Probability;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// How many mates the members of a [`Race`](crate::data::character::race::Race) have & how long they stay together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BondingModel {
+    /// Each [`Character`](crate::data::character::Character) has one mate at a time & stays with it until death.
+    #[default]
+    Monogamous,
+    /// Each [`Character`](crate::data::character::Character) has one mate at a time,
+    /// but each year the mates can separate with a probability.
+    Serial { separation: Probability },
+    /// A male has up to *max_mates* females, who only have him as mate.
+    Polygynous { max_mates: u32 },
+    /// A female has up to *max_mates* males, who only have her as mate.
+    Polyandrous { max_mates: u32 },
+    /// A [`Character`](crate::data::character::Character) of any gender leads a harem of up to *max_mates* mates,
+    /// who only have the leader as mate.
+    Harem { max_mates: u32 },
+    /// [`Characters`](crate::data::character::Character) don't bond & separate after their offspring is born.
+    NoBonding,
+}
+
+/// The pair-bonding of a [`Race`](crate::data::character::race::Race).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "PairBondingData")]
+pub struct PairBonding {
+    model: BondingModel,
+    /// Can [`Characters`](crate::data::character::Character) of the same gender become mates?
+    same_gender: bool,
+}
+
+/// The unvalidated fields of a [`PairBonding`], which are deserialized first. Both are optional.
+#[derive(Deserialize)]
+struct PairBondingData {
+    #[serde(default)]
+    model: BondingModel,
+    #[serde(default)]
+    same_gender: bool,
+}
+
+impl PairBonding {
+    /// Creates a pair-bonding, if valid:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::bonding::BondingModel::*;
+    ///# use age_of_dragons_core::data::character::race::bonding::PairBonding;
+    /// assert!(PairBonding::new(Monogamous, false).is_ok());
+    /// assert!(PairBonding::new(Polygynous { max_mates: 3 }, true).is_ok());
+    /// assert!(PairBonding::new(Polyandrous { max_mates: 0 }, false).is_err());
+    /// assert!(PairBonding::new(Harem { max_mates: 0 }, false).is_err());
+    /// ```
+    pub fn new(model: BondingModel, same_gender: bool) -> Result<Self> {
+        match model {
+            BondingModel::Polygynous { max_mates: 0 }
+            | BondingModel::Polyandrous { max_mates: 0 }
+            | BondingModel::Harem { max_mates: 0 } => {
+                bail!("The maximum number of mates must be greater than 0!")
+            }
+            _ => {}
+        }
+
+        Ok(Self { model, same_gender })
+    }
+
+    pub fn model(&self) -> BondingModel {
+        self.model
+    }
+
+    pub fn same_gender(&self) -> bool {
+        self.same_gender
+    }
+
+    /// Can 2 [`Characters`](crate::data::character::Character) with these genders become mates?
+    /// Hermaphrodites always mate with each other.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    ///# use age_of_dragons_core::data::character::race::bonding::BondingModel::Monogamous;
+    ///# use age_of_dragons_core::data::character::race::bonding::PairBonding;
+    /// let bonding = PairBonding::new(Monogamous, false).unwrap();
+    /// let same_gender = PairBonding::new(Monogamous, true).unwrap();
+    ///
+    /// assert!(bonding.allows_genders(Female, Male));
+    /// assert!(!bonding.allows_genders(Female, Female));
+    /// assert!(same_gender.allows_genders(Female, Female));
+    /// assert!(bonding.allows_genders(Hermaphrodite, Hermaphrodite));
+    /// ```
+    pub fn allows_genders(&self, gender0: Gender, gender1: Gender) -> bool {
+        self.same_gender || gender0 != gender1 || gender0 == Gender::Hermaphrodite
+    }
+
+    /// The maximum number of mates a [`Character`](crate::data::character::Character) of this gender can have at the same time.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    ///# use age_of_dragons_core::data::character::race::bonding::BondingModel::*;
+    ///# use age_of_dragons_core::data::character::race::bonding::PairBonding;
+    /// let polygynous = PairBonding::new(Polygynous { max_mates: 3 }, false).unwrap();
+    /// let harem = PairBonding::new(Harem { max_mates: 4 }, false).unwrap();
+    ///
+    /// assert_eq!(PairBonding::default().max_mates(Female), 1);
+    /// assert_eq!(polygynous.max_mates(Female), 1);
+    /// assert_eq!(polygynous.max_mates(Male), 3);
+    /// assert_eq!(harem.max_mates(Female), 4);
+    /// ```
+    pub fn max_mates(&self, gender: Gender) -> usize {
+        match (self.model, gender) {
+            (BondingModel::Polygynous { max_mates }, Gender::Male)
+            | (BondingModel::Polyandrous { max_mates }, Gender::Female)
+            | (BondingModel::Harem { max_mates }, _) => max_mates as usize,
+            _ => 1,
+        }
+    }
+
+    /// The yearly probability of mates separating, if they can.
+    pub fn separation(&self) -> Option<Probability> {
+        match self.model {
+            BondingModel::Serial { separation } => Some(separation),
+            _ => None,
+        }
+    }
+
+    /// Do mates separate after their offspring is born?
+    pub fn separates_after_birth(&self) -> bool {
+        self.model == BondingModel::NoBonding
+    }
+}
+
+impl TryFrom<PairBondingData> for PairBonding {
+    type Error = anyhow::Error;
+
+    fn try_from(data: PairBondingData) -> Result<Self> {
+        PairBonding::new(data.model, data.same_gender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let load = |string| ron::from_str::<PairBonding>(string).unwrap();
+
+        assert_eq!(load("()"), PairBonding::default());
+        assert_eq!(
+            load("(model: Harem(max_mates: 3), same_gender: true)"),
+            PairBonding::new(BondingModel::Harem { max_mates: 3 }, true).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(ron::from_str::<PairBonding>("(model: Harem(max_mates: 0))").is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use crate::data::character::race::bonding::PairBonding;
+use crate::data::character::race::gender::GenderOption;
+use crate::data::character::race::mortality::Mortality;
+use crate::data::character::race::naming::NamingRule;
+use crate::data::character::race::reproduction::ReproductionOption;
+use crate::data::character::race::stage::LifeStage;
+use crate::data::character::race::{Race, RaceId};
+use crate::data::time::Duration;
+use crate::generation::name::NameGenerator;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// The definition of a [`Race`] in a data file, which is validated when creating the race.
+/// Its fields are validated while parsing, but the ids of the race & its life stages are only known when creating it.
+///
+/// ```
+///# use age_of_dragons_core::data::character::race::definition::RaceDefinition;
+///# use age_of_dragons_core::data::character::race::RaceId;
+/// let definition = RaceDefinition::parse(r#"
+/// #![enable(implicit_some)]
+/// (
+///     name: "Giant Spider",
+///     gender_option: TwoGenders,
+///     names: (beginnings: ["Shel", "Ix"], endings: ["ob", "ara"]),
+///     stages: [
+///         (name: "Spiderling", max_age: (months: 6)),
+///         (
+///             name: "Adult",
+///             reproduction: (
+///                 probability: (threshold: 1, max: 4),
+///                 offspring_count: Range(min: 10, max: 20),
+///             ),
+///         ),
+///     ],
+/// )
+/// "#).unwrap();
+/// let race = definition.create(RaceId::new(3)).unwrap();
+///
+/// assert_eq!(race.name().to_str(), "Giant Spider");
+/// assert_eq!(race.stages().len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct RaceDefinition {
+    name: String,
+    gender_option: GenderOption,
+    stages: Vec<LifeStageDefinition>,
+    #[serde(default)]
+    names: Option<NameGenerator>,
+    #[serde(default)]
+    naming_rule: NamingRule,
+    #[serde(default)]
+    bonding: PairBonding,
+}
+
+/// The definition of a [`LifeStage`] in a data file. Its index is the position in the list.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct LifeStageDefinition {
+    name: String,
+    #[serde(default)]
+    max_age: Option<Duration>,
+    #[serde(default)]
+    reproduction: Option<ReproductionOption>,
+    #[serde(default)]
+    mortality: Vec<Mortality>,
+}
+
+impl RaceDefinition {
+    /// Parses a definition in the RON format, which validates everything except the race itself.
+    pub fn parse(string: &str) -> Result<Self> {
+        ron::from_str(string).context("Failed to parse race definition")
+    }
+
+    /// Creates a [`Race`] from the definition, if valid.
+    pub fn create(self, id: RaceId) -> Result<Race> {
+        let name = self.name;
+        let stages = self
+            .stages
+            .into_iter()
+            .enumerate()
+            .map(|(index, stage)| stage.create(index))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("Failed to create race '{}'", name))?;
+
+        Race::new(
+            id.id(),
+            name.clone(),
+            self.gender_option,
+            stages,
+            self.names,
+            self.naming_rule,
+            self.bonding,
+        )
+        .with_context(|| format!("Failed to create race '{}'", name))
+    }
+}
+
+impl LifeStageDefinition {
+    fn create(self, index: usize) -> Result<LifeStage> {
+        LifeStage::new(
+            self.name,
+            index,
+            self.max_age,
+            self.reproduction,
+            self.mortality,
+        )
+        .with_context(|| format!("Invalid field 'stages[{}]'", index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::character::race::bonding::PairBonding;
+    use crate::data::character::race::manager::RaceMgr;
+    use crate::data::character::race::naming::NamingRule;
+    use crate::data::character::race::reproduction::count::OffspringCount;
+    use crate::data::probability::Probability;
+    use crate::data::time::Duration;
+    use std::path::Path;
+
+    #[test]
+    fn test_load_dragon() {
+        let mut manager = RaceMgr::default();
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/races/dragon.ron");
+
+        let id = manager.load(&path).unwrap();
+        let race = manager.get(id).unwrap();
+
+        assert_eq!(race.name().to_str(), "Dragon");
+        assert_eq!(race.stages().len(), 5);
+        assert!(race.stages()[2].reproduction().is_some());
+        assert_eq!(race.stages()[0].max_age(), &Some(Duration::new(4)));
+        assert!(race.name_generator().is_some());
+        assert_eq!(race.naming_rule(), NamingRule::Patronymic);
+        assert_eq!(race.bonding(), &PairBonding::default());
+    }
+
+    #[test]
+    fn test_bonding() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)").replace(
+            "stages: [",
+            "bonding: (model: Serial(separation: (threshold: 1, max: 20)), same_gender: true),\n stages: [",
+        );
+        let mut manager = RaceMgr::default();
+
+        let id = manager.load_from_str(&string).unwrap();
+        let bonding = manager.get(id).unwrap().bonding();
+
+        assert_eq!(bonding.separation(), Some(Probability::new(1, 20).unwrap()));
+        assert!(bonding.same_gender());
+    }
+
+    #[test]
+    fn test_invalid_bonding() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)").replace(
+            "stages: [",
+            "bonding: (model: Harem(max_mates: 0)),\n stages: [",
+        );
+
+        assert_parse_error(
+            &string,
+            "The maximum number of mates must be greater than 0!",
+        );
+    }
+
+    #[test]
+    fn test_max_age_with_months() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)")
+            .replace("(years: 10)", "(years: 1, months: 3)");
+        let mut manager = RaceMgr::default();
+
+        let id = manager.load_from_str(&string).unwrap();
+        let race = manager.get(id).unwrap();
+
+        assert_eq!(race.stages()[0].max_age(), &Some(Duration::from_months(15)));
+    }
+
+    #[test]
+    fn test_invalid_probability() {
+        let string = create_string("(threshold: 0, max: 4)", "Fixed(1)");
+
+        assert_parse_error(&string, "The threshold must be greater than 0!");
+    }
+
+    #[test]
+    fn test_invalid_offspring_count() {
+        let string = create_string("(threshold: 1, max: 4)", "Range(min: 5, max: 4)");
+
+        assert_parse_error(&string, "The maximum 4 must be greater than the minimum 5!");
+    }
+
+    #[test]
+    fn test_weighted_offspring_count() {
+        let string = create_string("(threshold: 1, max: 4)", "Weighted([(1, 60), (2, 30)])");
+        let mut manager = RaceMgr::default();
+
+        let id = manager.load_from_str(&string).unwrap();
+        let reproduction = manager.get(id).unwrap().stages()[1].reproduction();
+
+        assert_eq!(
+            reproduction.as_ref().unwrap().offspring_count(),
+            &OffspringCount::Weighted(vec![(1, 60), (2, 30)])
+        );
+    }
+
+    #[test]
+    fn test_invalid_binomial_offspring_count() {
+        let string = create_string(
+            "(threshold: 1, max: 4)",
+            "Binomial(min: 1, trials: 3, probability: (threshold: 0, max: 4))",
+        );
+
+        assert_parse_error(&string, "The threshold must be greater than 0!");
+    }
+
+    #[test]
+    fn test_fertility_and_cooldown() {
+        let string = create_string(
+            "(threshold: 1, max: 4), fertility: [(age: (years: 20), percentage: 50), (age: (years: 30), percentage: 100)], cooldown: (months: 18)",
+            "Fixed(1)",
+        );
+        let mut manager = RaceMgr::default();
+
+        let id = manager.load_from_str(&string).unwrap();
+        let stage = &manager.get(id).unwrap().stages()[1];
+        let reproduction = stage.reproduction().as_ref().unwrap();
+
+        assert_eq!(
+            reproduction.fertility().get_percentage(Duration::new(25)),
+            75
+        );
+        assert_eq!(reproduction.cooldown(), Duration::from_months(18));
+    }
+
+    #[test]
+    fn test_invalid_fertility() {
+        let string = create_string(
+            "(threshold: 1, max: 4), fertility: [(age: (years: 30), percentage: 50), (age: (years: 20), percentage: 100)]",
+            "Fixed(1)",
+        );
+
+        assert_parse_error(&string, "is not after");
+    }
+
+    #[test]
+    fn test_fertility_outside_stage() {
+        let string = create_string(
+            "(threshold: 1, max: 4), fertility: [(age: (years: 5), percentage: 50)]",
+            "Fixed(1)",
+        );
+
+        assert_error(
+            &string,
+            "Race Test's life stage 1 has fertility points outside its ages!",
+        );
+    }
+
+    #[test]
+    fn test_invalid_stage_name() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)").replace("Adult", " ");
+
+        assert_error(&string, "Invalid field 'stages[1]'");
+        assert_error(&string, "The name ' ' is invalid!");
+    }
+
+    #[test]
+    fn test_invalid_stage_order() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)")
+            .replace("Some((years: 10))", "None");
+
+        assert_error(
+            &string,
+            "Race Test's life stage 0 has no max age, but is not last!",
+        );
+    }
+
+    #[test]
+    fn test_invalid_mortality() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)").replace(
+            "(name: \"Child\", max_age: Some((years: 10)))",
+            "(name: \"Child\", max_age: Some((years: 10)), mortality: [(cause: Disease, probability: (threshold: 2, max: 1))])",
+        );
+
+        assert_parse_error(
+            &string,
+            "The maximum 1 must not be smaller than the threshold 2!",
+        );
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)")
+            .replace("stages: [", "names: Some((beginnings: [])),\n stages: [");
+
+        assert_parse_error(&string, "The name generator has no beginnings!");
+    }
+
+    #[test]
+    fn test_invalid_gender_option() {
+        let string = create_string("(threshold: 1, max: 4)", "Fixed(1)").replace(
+            "TwoGenders",
+            "GenderChange(initial: Male, changed: Female, stage: 0)",
+        );
+
+        assert_error(&string, "Race Test has an invalid gender option!");
+    }
+
+    #[test]
+    fn test_invalid_syntax() {
+        let mut manager = RaceMgr::default();
+
+        assert!(manager.load_from_str("(name: \"Test\"").is_err());
+    }
+
+    #[test]
+    fn test_unknown_file() {
+        let mut manager = RaceMgr::default();
+        let error = manager.load(Path::new("unknown.ron")).unwrap_err();
+
+        assert!(format!("{:?}", error).contains("unknown.ron"));
+    }
+
+    fn create_string(probability: &str, count: &str) -> String {
+        format!(
+            "(
+                name: \"Test\",
+                gender_option: TwoGenders,
+                stages: [
+                    (name: \"Child\", max_age: Some((years: 10))),
+                    (
+                        name: \"Adult\",
+                        reproduction: Some((probability: {}, offspring_count: {})),
+                    ),
+                ],
+            )",
+            probability, count
+        )
+    }
+
+    fn assert_error(string: &str, message: &str) {
+        let mut manager = RaceMgr::default();
+        let error = format!("{:?}", manager.load_from_str(string).unwrap_err());
+
+        assert!(error.contains("Failed to create race 'Test'"), "{}", error);
+        assert!(error.contains(message), "{}", error);
+    }
+
+    fn assert_parse_error(string: &str, message: &str) {
+        let mut manager = RaceMgr::default();
+        let error = format!("{:?}", manager.load_from_str(string).unwrap_err());
+
+        assert!(
+            error.contains("Failed to parse race definition"),
+            "{}",
+            error
+        );
+        assert!(error.contains(message), "{}", error);
+    }
+}
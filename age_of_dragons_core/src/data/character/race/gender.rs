@@ -1,19 +1,108 @@
 use crate::data::character::gender::Gender;
+use crate::data::character::race::stage::LifeStageId;
+use crate::generation::number::RandomNumberGenerator;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
 
 /// Which [`Gender`]s are available for members of this [`Race`](crate::data::character::race::Race)?
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "GenderOptionData")]
 pub enum GenderOption {
-    /// The [`Race`](crate::data::character::race::Race) has no gender, which limits the reproduction options.
+    /// The [`Race`](crate::data::character::race::Race) has no gender, so its members reproduce asexually.
     NoGender,
     /// The [`Race`](crate::data::character::race::Race) has males & females.
     TwoGenders,
+    /// The [`Race`](crate::data::character::race::Race) has males & females,
+    /// which are born with a ratio of *females* to *males*.
+    SexRatio { females: u32, males: u32 },
+    /// All members of the [`Race`](crate::data::character::race::Race) are hermaphrodites.
+    Hermaphrodite,
+    /// The members of the [`Race`](crate::data::character::race::Race) are born with the *initial* gender
+    /// and change to the other one, when reaching the life stage with index *stage*.
+    GenderChange {
+        initial: Gender,
+        changed: Gender,
+        stage: usize,
+    },
+}
+
+/// The unvalidated variants of a [`GenderOption`], which are deserialized first.
+#[derive(Deserialize)]
+enum GenderOptionData {
+    NoGender,
+    TwoGenders,
+    SexRatio {
+        females: u32,
+        males: u32,
+    },
+    Hermaphrodite,
+    GenderChange {
+        initial: Gender,
+        changed: Gender,
+        stage: usize,
+    },
 }
 
 impl GenderOption {
+    /// Validates the option for a [`Race`](crate::data::character::race::Race) with a number of life stages:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::gender::GenderOption::*;
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    /// assert!(TwoGenders.validate(1).is_ok());
+    /// assert!(SexRatio { females: 3, males: 1 }.validate(1).is_ok());
+    /// assert!(SexRatio { females: 0, males: 0 }.validate(1).is_err());
+    /// assert!(SexRatio { females: u32::MAX, males: 1 }.validate(1).is_err());
+    /// assert!(GenderChange { initial: Male, changed: Female, stage: 1 }.validate(2).is_ok());
+    /// assert!(GenderChange { initial: Male, changed: Female, stage: 0 }.validate(2).is_err());
+    /// assert!(GenderChange { initial: Male, changed: Female, stage: 2 }.validate(2).is_err());
+    /// assert!(GenderChange { initial: Male, changed: Male, stage: 1 }.validate(2).is_err());
+    /// assert!(GenderChange { initial: Male, changed: Genderless, stage: 1 }.validate(2).is_err());
+    /// ```
+    pub fn validate(&self, stages: usize) -> Result<()> {
+        self.validate_genders()?;
+
+        if let GenderOption::GenderChange { stage, .. } = *self {
+            if stage == 0 || stage >= stages {
+                bail!("The gender can't change at life stage {}!", stage);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates everything, that doesn't depend on the life stages of the [`Race`](crate::data::character::race::Race).
+    fn validate_genders(&self) -> Result<()> {
+        match *self {
+            GenderOption::SexRatio { females, males } => match females.checked_add(males) {
+                Some(0) => bail!("The sex ratio needs females or males!"),
+                None => bail!("The sex ratio {}:{} is too large!", females, males),
+                Some(_) => {}
+            },
+            GenderOption::GenderChange {
+                initial, changed, ..
+            } if !is_female_or_male(initial)
+                || !is_female_or_male(changed)
+                || initial == changed =>
+            {
+                bail!(
+                    "The gender can't change from {:?} to {:?}!",
+                    initial,
+                    changed
+                )
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     /// Is the [`Gender`] valid for this option?
     ///
     /// ```
+    ///# use age_of_dragons_core::data::character::race::gender::GenderOption;
     ///# use age_of_dragons_core::data::character::race::gender::GenderOption::*;
+    ///# use age_of_dragons_core::data::character::gender::Gender;
     ///# use age_of_dragons_core::data::character::gender::Gender::*;
     ///
     /// assert!(!NoGender.is_valid(Female));
@@ -23,11 +112,144 @@ impl GenderOption {
     /// assert!(TwoGenders.is_valid(Female));
     /// assert!(TwoGenders.is_valid(Male));
     /// assert!(!TwoGenders.is_valid(Genderless));
+    /// assert!(!TwoGenders.is_valid(Gender::Hermaphrodite));
+    ///
+    /// assert!(GenderOption::Hermaphrodite.is_valid(Gender::Hermaphrodite));
+    /// assert!(!GenderOption::Hermaphrodite.is_valid(Female));
     /// ```
     pub fn is_valid(&self, gender: Gender) -> bool {
         match self {
             GenderOption::NoGender => gender == Gender::Genderless,
-            GenderOption::TwoGenders => gender == Gender::Female || gender == Gender::Male,
+            GenderOption::TwoGenders | GenderOption::SexRatio { .. } => is_female_or_male(gender),
+            GenderOption::Hermaphrodite => gender == Gender::Hermaphrodite,
+            GenderOption::GenderChange {
+                initial, changed, ..
+            } => gender == *initial || gender == *changed,
+        }
+    }
+
+    /// Generates a valid [`Gender`] for a newborn.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::gender::GenderOption;
+    ///# use age_of_dragons_core::data::character::race::gender::GenderOption::*;
+    ///# use age_of_dragons_core::data::character::gender::Gender;
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    ///# use age_of_dragons_core::generation::number::RandomNumberGenerator;
+    /// let rng = RandomNumberGenerator::Mock {values: vec![0, 1, 2, 3], default: 0};
+    /// let ratio = SexRatio { females: 3, males: 1 };
+    /// let change = GenderChange { initial: Male, changed: Female, stage: 1 };
+    ///
+    /// assert_eq!(NoGender.generate(&rng, 0), Genderless);
+    /// assert_eq!(NoGender.generate(&rng, 1), Genderless);
+    /// assert_eq!(TwoGenders.generate(&rng, 0), Female);
+    /// assert_eq!(TwoGenders.generate(&rng, 1), Male);
+    /// assert_eq!(ratio.generate(&rng, 2), Female);
+    /// assert_eq!(ratio.generate(&rng, 3), Male);
+    /// assert_eq!(GenderOption::Hermaphrodite.generate(&rng, 0), Gender::Hermaphrodite);
+    /// assert_eq!(change.generate(&rng, 1), Male);
+    /// ```
+    pub fn generate(&self, rng: &RandomNumberGenerator, index: usize) -> Gender {
+        match *self {
+            GenderOption::NoGender => Gender::Genderless,
+            GenderOption::TwoGenders => {
+                if rng.generate(index, 2) == 0 {
+                    Gender::Female
+                } else {
+                    Gender::Male
+                }
+            }
+            GenderOption::SexRatio { females, males } => {
+                if rng.generate(index, females + males) < females {
+                    Gender::Female
+                } else {
+                    Gender::Male
+                }
+            }
+            GenderOption::Hermaphrodite => Gender::Hermaphrodite,
+            GenderOption::GenderChange { initial, .. } => initial,
         }
     }
+
+    /// Returns the new [`Gender`], if it changes when reaching the life stage.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::gender::GenderOption::*;
+    ///# use age_of_dragons_core::data::character::race::stage::LifeStageId;
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    /// let change = GenderChange { initial: Male, changed: Female, stage: 2 };
+    ///
+    /// assert_eq!(change.get_changed_gender(LifeStageId::new(1)), None);
+    /// assert_eq!(change.get_changed_gender(LifeStageId::new(2)), Some(Female));
+    /// assert_eq!(TwoGenders.get_changed_gender(LifeStageId::new(2)), None);
+    /// ```
+    pub fn get_changed_gender(&self, stage: LifeStageId) -> Option<Gender> {
+        match *self {
+            GenderOption::GenderChange {
+                changed,
+                stage: change_stage,
+                ..
+            } if change_stage == stage.index() => Some(changed),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<GenderOptionData> for GenderOption {
+    type Error = anyhow::Error;
+
+    fn try_from(data: GenderOptionData) -> Result<Self> {
+        let option = match data {
+            GenderOptionData::NoGender => GenderOption::NoGender,
+            GenderOptionData::TwoGenders => GenderOption::TwoGenders,
+            GenderOptionData::SexRatio { females, males } => {
+                GenderOption::SexRatio { females, males }
+            }
+            GenderOptionData::Hermaphrodite => GenderOption::Hermaphrodite,
+            GenderOptionData::GenderChange {
+                initial,
+                changed,
+                stage,
+            } => GenderOption::GenderChange {
+                initial,
+                changed,
+                stage,
+            },
+        };
+
+        option.validate_genders()?;
+
+        Ok(option)
+    }
+}
+
+fn is_female_or_male(gender: Gender) -> bool {
+    gender == Gender::Female || gender == Gender::Male
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let option: GenderOption = ron::from_str("SexRatio(females: 3, males: 1)").unwrap();
+
+        assert_eq!(
+            option,
+            GenderOption::SexRatio {
+                females: 3,
+                males: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(ron::from_str::<GenderOption>("SexRatio(females: 0, males: 0)").is_err());
+        assert!(ron::from_str::<GenderOption>(
+            "GenderChange(initial: Male, changed: Male, stage: 1)"
+        )
+        .is_err());
+    }
 }
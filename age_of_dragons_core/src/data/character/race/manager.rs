@@ -1,31 +1,53 @@
+use crate::data::character::race::definition::RaceDefinition;
 use crate::data::character::race::stage::LifeStage;
 use crate::data::character::race::{Race, RaceId};
 use crate::data::character::Character;
-use anyhow::Result;
+use crate::data::storage::Storage;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
 
 /// Stores all the [`Race`]s.
-#[derive(Default, Debug)]
-pub struct RaceMgr {
-    races: Vec<Race>,
-}
+pub type RaceMgr = Storage<RaceId, Race>;
 
 impl RaceMgr {
-    /// Uses the function *f* to create a [`Race`] with the next [`RaceId`].
-    pub fn create<F>(&mut self, f: F) -> Result<RaceId>
-    where
-        F: FnOnce(RaceId) -> Result<Race>,
-    {
-        let id = RaceId::new(self.races.len());
-        self.races.push(f(id)?);
-        Ok(id)
+    /// Creates a [`Race`] from a [`definition`](RaceDefinition) file.
+    pub fn load(&mut self, path: &Path) -> Result<RaceId> {
+        let string = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read race file {:?}", path))?;
+        self.load_from_str(&string)
+            .with_context(|| format!("Failed to load race file {:?}", path))
     }
 
-    pub fn get_all(&self) -> &Vec<Race> {
-        &self.races
+    /// Creates a [`Race`] for each [`definition`](RaceDefinition) file with the extension *ron* in a directory.
+    /// The files are loaded in alphabetical order.
+    pub fn load_dir(&mut self, path: &Path) -> Result<Vec<RaceId>> {
+        let mut paths = fs::read_dir(path)
+            .with_context(|| format!("Failed to read race directory {:?}", path))?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Failed to read race directory {:?}", path))?;
+        paths.retain(|path| path.extension().map(|e| e == "ron").unwrap_or(false));
+        paths.sort();
+
+        paths.iter().map(|path| self.load(path)).collect()
     }
 
-    pub fn get(&self, id: RaceId) -> Option<&Race> {
-        self.races.get(id.0)
+    /// Creates a [`Race`] from a [`definition`](RaceDefinition) in the RON format.
+    pub fn load_from_str(&mut self, string: &str) -> Result<RaceId> {
+        let definition = RaceDefinition::parse(string)?;
+        self.create(|id| definition.create(id))
+    }
+
+    /// Validates that each [`Race`] is stored at the index of its id, which can't be done while deserializing it.
+    pub fn validate(&self) -> Result<()> {
+        for (index, race) in self.get_all().iter().enumerate() {
+            if race.id().id() != index {
+                bail!("Race {} is stored at index {}!", race.id().id(), index);
+            }
+        }
+
+        Ok(())
     }
 
     /// Returns the [`LifeStage`] of the [`Character`].
@@ -1,16 +1,25 @@
+use crate::data::character::race::bonding::PairBonding;
 use crate::data::character::race::gender::GenderOption;
+use crate::data::character::race::naming::NamingRule;
 use crate::data::character::race::stage::LifeStage;
 use crate::data::name::Name;
+use crate::data::storage::StorageId;
 use crate::data::time::Duration;
+use crate::generation::name::NameGenerator;
 use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
 
+pub mod bonding;
+pub mod definition;
 pub mod gender;
 pub mod manager;
+pub mod mortality;
+pub mod naming;
 pub mod reproduction;
 pub mod stage;
 
 /// The id of a [`Race`].
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct RaceId(usize);
 
 impl RaceId {
@@ -23,14 +32,41 @@ impl RaceId {
     }
 }
 
+impl StorageId for RaceId {
+    fn new(id: usize) -> Self {
+        Self(id)
+    }
+
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+
 /// The race of a [`Character`](crate::data::character::Character).
 /// Examples are dragon, thunder bird & giant spider.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RaceData")]
 pub struct Race {
     id: RaceId,
     name: Name,
     gender_option: GenderOption,
     stages: Vec<LifeStage>,
+    /// Generates the names of newborns. Otherwise they are numbered.
+    name_generator: Option<NameGenerator>,
+    naming_rule: NamingRule,
+    bonding: PairBonding,
+}
+
+/// The unvalidated fields of a [`Race`], which are deserialized first.
+#[derive(Deserialize)]
+struct RaceData {
+    id: RaceId,
+    name: Name,
+    gender_option: GenderOption,
+    stages: Vec<LifeStage>,
+    name_generator: Option<NameGenerator>,
+    naming_rule: NamingRule,
+    bonding: PairBonding,
 }
 
 impl Race {
@@ -40,6 +76,9 @@ impl Race {
         name: S,
         gender_option: GenderOption,
         stages: Vec<LifeStage>,
+        name_generator: Option<NameGenerator>,
+        naming_rule: NamingRule,
+        bonding: PairBonding,
     ) -> Result<Self> {
         let name = name.into();
 
@@ -73,9 +112,25 @@ impl Race {
                 bail!("Race {}'s life stage {} has wrong index!", name, i);
             }
 
+            if let Some(reproduction) = stage.reproduction() {
+                let start = previous_max_age.unwrap_or_default();
+
+                if !reproduction.fertility().is_within(start, *stage.max_age()) {
+                    bail!(
+                        "Race {}'s life stage {} has fertility points outside its ages!",
+                        name,
+                        i
+                    );
+                }
+            }
+
             previous_max_age = *stage.max_age();
         }
 
+        gender_option
+            .validate(stages.len())
+            .with_context(|| format!("Race {} has an invalid gender option!", name))?;
+
         let name = Name::new(name).with_context(|| format!("Failed to create race {}", id))?;
 
         Ok(Self {
@@ -83,6 +138,9 @@ impl Race {
             name,
             gender_option,
             stages,
+            name_generator,
+            naming_rule,
+            bonding,
         })
     }
 
@@ -93,6 +151,9 @@ impl Race {
             name: Name::new(format!("Race {}", id)).unwrap(),
             gender_option,
             stages: vec![LifeStage::simple()],
+            name_generator: None,
+            naming_rule: NamingRule::Single,
+            bonding: PairBonding::default(),
         }
     }
 
@@ -112,17 +173,30 @@ impl Race {
         &self.stages
     }
 
+    pub fn name_generator(&self) -> Option<&NameGenerator> {
+        self.name_generator.as_ref()
+    }
+
+    pub fn naming_rule(&self) -> NamingRule {
+        self.naming_rule
+    }
+
+    pub fn bonding(&self) -> &PairBonding {
+        &self.bonding
+    }
+
     /// Calculates the [`LifeStage`] of a [`Character`](crate::data::character::Character) based on its age.
     ///
     /// ```
     ///# use age_of_dragons_core::data::character::race::Race;
     ///# use age_of_dragons_core::data::character::race::gender::GenderOption::*;
+    ///# use age_of_dragons_core::data::character::race::naming::NamingRule::Single;
     ///# use age_of_dragons_core::data::character::race::stage::LifeStage;
     ///# use age_of_dragons_core::data::name::Name;
     ///# use age_of_dragons_core::data::time::Duration;
-    /// let stage0 = LifeStage::new("LS0", 0, Some(Duration::new(1)), None).unwrap();
-    /// let stage1 = LifeStage::new("LS1", 1, Some(Duration::new(3)), None).unwrap();
-    /// let race = Race::new(32, "R0", TwoGenders, vec![stage0.clone(), stage1.clone()]).unwrap();
+    /// let stage0 = LifeStage::new("LS0", 0, Some(Duration::new(1)), None, vec![]).unwrap();
+    /// let stage1 = LifeStage::new("LS1", 1, Some(Duration::new(3)), None, vec![]).unwrap();
+    /// let race = Race::new(32, "R0", TwoGenders, vec![stage0.clone(), stage1.clone()], None, Single, Default::default()).unwrap();
     ///
     /// assert_eq!(race.calculate_life_stage(&Duration::new(0)), Some(&stage0));
     /// assert_eq!(race.calculate_life_stage(&Duration::new(1)), Some(&stage0));
@@ -145,80 +219,283 @@ impl Race {
     }
 }
 
+impl TryFrom<RaceData> for Race {
+    type Error = anyhow::Error;
+
+    fn try_from(data: RaceData) -> Result<Self> {
+        Race::new(
+            data.id.id(),
+            data.name,
+            data.gender_option,
+            data.stages,
+            data.name_generator,
+            data.naming_rule,
+            data.bonding,
+        )
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::data::character::gender::Gender;
     use crate::data::character::race::gender::GenderOption::NoGender;
     use crate::data::character::race::manager::RaceMgr;
     use crate::data::character::race::reproduction::count::OffspringCount;
+    use crate::data::character::race::reproduction::fertility::FertilityCurve;
     use crate::data::character::race::reproduction::ReproductionOption;
     use crate::data::probability::Probability;
     use GenderOption::TwoGenders;
+    use NamingRule::Single;
 
     #[test]
     fn test_new() {
-        let stage0 = LifeStage::new("LF0", 0, Some(Duration::new(44)), None).unwrap();
-        let stage1 = LifeStage::new("LF1", 1, None, None).unwrap();
+        let stage0 = LifeStage::new("LF0", 0, Some(Duration::new(44)), None, vec![]).unwrap();
+        let stage1 = LifeStage::new("LF1", 1, None, None, vec![]).unwrap();
 
-        assert!(Race::new(0, "Test", TwoGenders, vec![stage0, stage1]).is_ok());
+        assert!(TestRace::with_stages(vec![stage0, stage1]).build(0).is_ok());
     }
 
     #[test]
     fn test_new_with_invalid_name() {
-        let stage = LifeStage::new("LF", 1, None, None).unwrap();
+        let stage = LifeStage::new("LF", 1, None, None, vec![]).unwrap();
+        let race = TestRace {
+            name: "",
+            ..TestRace::with_stages(vec![stage])
+        };
 
-        assert!(Race::new(0, "", TwoGenders, vec![stage]).is_err());
+        assert!(race.build(0).is_err());
     }
 
     #[test]
     fn test_new_without_stages() {
-        assert!(Race::new(0, "Test", TwoGenders, vec![]).is_err());
+        assert!(TestRace::with_stages(vec![]).build(0).is_err());
     }
 
     #[test]
     fn test_new_with_early_stage_is_endless() {
-        let stage0 = LifeStage::new("LF0", 0, None, None).unwrap();
-        let stage1 = LifeStage::new("LF1", 1, None, None).unwrap();
+        let stage0 = LifeStage::new("LF0", 0, None, None, vec![]).unwrap();
+        let stage1 = LifeStage::new("LF1", 1, None, None, vec![]).unwrap();
 
-        assert!(Race::new(0, "Test", TwoGenders, vec![stage0, stage1]).is_err());
+        assert!(TestRace::with_stages(vec![stage0, stage1])
+            .build(0)
+            .is_err());
     }
 
     #[test]
     fn test_new_with_early_stage_ends_after_later_stage() {
-        let stage0 = LifeStage::new("LF0", 0, Some(Duration::new(20)), None).unwrap();
-        let stage1 = LifeStage::new("LF1", 1, Some(Duration::new(10)), None).unwrap();
+        let stage0 = LifeStage::new("LF0", 0, Some(Duration::new(20)), None, vec![]).unwrap();
+        let stage1 = LifeStage::new("LF1", 1, Some(Duration::new(10)), None, vec![]).unwrap();
 
-        assert!(Race::new(0, "Test", TwoGenders, vec![stage0, stage1]).is_err());
+        assert!(TestRace::with_stages(vec![stage0, stage1])
+            .build(0)
+            .is_err());
     }
 
     #[test]
     fn test_new_with_wrong_index() {
-        let stage0 = LifeStage::new("LF0", 1, Some(Duration::new(44)), None).unwrap();
-        let stage1 = LifeStage::new("LF1", 0, None, None).unwrap();
+        let stage0 = LifeStage::new("LF0", 1, Some(Duration::new(44)), None, vec![]).unwrap();
+        let stage1 = LifeStage::new("LF1", 0, None, None, vec![]).unwrap();
 
-        assert!(Race::new(0, "Test", TwoGenders, vec![stage0, stage1]).is_err());
+        assert!(TestRace::with_stages(vec![stage0, stage1])
+            .build(0)
+            .is_err());
     }
 
-    pub fn create_mortal_race(manager: &mut RaceMgr, age0: u32, age1: u32) -> RaceId {
+    #[test]
+    fn test_new_with_invalid_gender_option() {
+        let stage0 = LifeStage::new("LF0", 0, Some(Duration::new(44)), None, vec![]).unwrap();
+        let stage1 = LifeStage::new("LF1", 1, None, None, vec![]).unwrap();
+        let race = TestRace {
+            gender_option: GenderOption::GenderChange {
+                initial: Gender::Male,
+                changed: Gender::Female,
+                stage: 2,
+            },
+            ..TestRace::with_stages(vec![stage0, stage1])
+        };
+
+        assert!(race.build(0).is_err());
+    }
+
+    #[test]
+    fn test_new_with_fertility_outside_stage() {
         let probability = Probability::new(1, 5).unwrap();
         let count = OffspringCount::new_fixed_count(1).unwrap();
-        let reproduction = ReproductionOption::new(probability, count);
-        let stage0 = LifeStage::new("Child", 0, Some(Duration::new(age0)), None).unwrap();
-        let stage1 =
-            LifeStage::new("Adult", 1, Some(Duration::new(age1)), Some(reproduction)).unwrap();
-        let stages = vec![stage0, stage1];
+        let create_race = |age| {
+            let fertility = FertilityCurve::new(vec![(Duration::new(age), 50)]).unwrap();
+            let reproduction =
+                ReproductionOption::new(probability, count.clone(), fertility, Duration::default());
+            let stage0 = LifeStage::new("LF0", 0, Some(Duration::new(10)), None, vec![]).unwrap();
+            let stage1 = LifeStage::new(
+                "LF1",
+                1,
+                Some(Duration::new(20)),
+                Some(reproduction),
+                vec![],
+            )
+            .unwrap();
+
+            TestRace::with_stages(vec![stage0, stage1]).build(0)
+        };
+
+        assert!(create_race(9).is_err());
+        assert!(create_race(10).is_ok());
+        assert!(create_race(20).is_ok());
+        assert!(create_race(21).is_err());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let mut manager = RaceMgr::default();
+        let id = create_mortal_race(&mut manager, 1, 3);
+        let race = manager.get(id).unwrap();
+        let string = ron::to_string(race).unwrap();
 
-        manager
-            .create(|id| Race::new(id.id(), "Mortal Race", TwoGenders, stages))
-            .unwrap()
+        assert_eq!(&ron::from_str::<Race>(&string).unwrap(), race);
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let mut manager = RaceMgr::default();
+        let id = create_mortal_race(&mut manager, 1, 3);
+        let string = ron::to_string(manager.get(id).unwrap()).unwrap();
+        let early_end = string.replacen("(years:3,months:0)", "(years:0,months:6)", 1);
+
+        assert_ne!(string, early_end);
+        assert!(ron::from_str::<Race>(&early_end).is_err());
+    }
+
+    pub fn create_mortal_race(manager: &mut RaceMgr, age0: u32, age1: u32) -> RaceId {
+        MortalRace {
+            child_age: age0,
+            adult_age: age1,
+            ..MortalRace::default()
+        }
+        .create(manager)
+    }
+
+    /// A race with a child & an adult life stage for testing. Only the fields differing from the default need to be set.
+    pub struct MortalRace {
+        /// The max age of the child stage in years.
+        pub child_age: u32,
+        /// The max age of the adult stage in years, after which members die of old age.
+        pub adult_age: u32,
+        pub gender_option: GenderOption,
+        pub name_generator: Option<NameGenerator>,
+        pub naming_rule: NamingRule,
+        pub bonding: PairBonding,
+        /// The reproduction of the adult stage.
+        pub reproduction: ReproductionOption,
+    }
+
+    impl Default for MortalRace {
+        fn default() -> Self {
+            let probability = Probability::new(1, 5).unwrap();
+            let count = OffspringCount::new_fixed_count(1).unwrap();
+
+            Self {
+                child_age: 1,
+                adult_age: 3,
+                gender_option: TwoGenders,
+                name_generator: None,
+                naming_rule: Single,
+                bonding: PairBonding::default(),
+                reproduction: ReproductionOption::simple(probability, count),
+            }
+        }
+    }
+
+    impl MortalRace {
+        pub fn create(self, manager: &mut RaceMgr) -> RaceId {
+            let child_age = Some(Duration::new(self.child_age));
+            let adult_age = Some(Duration::new(self.adult_age));
+            let stage0 = LifeStage::new("Child", 0, child_age, None, vec![]).unwrap();
+            let stage1 =
+                LifeStage::new("Adult", 1, adult_age, Some(self.reproduction), vec![]).unwrap();
+
+            TestRace {
+                name: "Mortal Race",
+                gender_option: self.gender_option,
+                name_generator: self.name_generator,
+                naming_rule: self.naming_rule,
+                bonding: self.bonding,
+                ..TestRace::with_stages(vec![stage0, stage1])
+            }
+            .create(manager)
+        }
+    }
+
+    /// Creates a genderless race, whose adults reproduce asexually.
+    pub fn create_asexual_race(manager: &mut RaceMgr, naming_rule: NamingRule) -> RaceId {
+        let probability = Probability::new(1, 2).unwrap();
+        let count = OffspringCount::new_fixed_count(2).unwrap();
+        let reproduction = ReproductionOption::simple(probability, count);
+        let stage0 = LifeStage::new("Young", 0, Some(Duration::new(1)), None, vec![]).unwrap();
+        let stage1 = LifeStage::new("Adult", 1, None, Some(reproduction), vec![]).unwrap();
+
+        TestRace {
+            name: "Slime",
+            gender_option: NoGender,
+            naming_rule,
+            ..TestRace::with_stages(vec![stage0, stage1])
+        }
+        .create(manager)
     }
 
     pub fn create_immortal_race(manager: &mut RaceMgr) -> RaceId {
-        let stage = LifeStage::new("Immortal", 0, None, None).unwrap();
-        let stages = vec![stage];
+        let stage = LifeStage::new("Immortal", 0, None, None, vec![]).unwrap();
 
-        manager
-            .create(|id| Race::new(id.id(), "Immortal Race", NoGender, stages))
-            .unwrap()
+        TestRace {
+            name: "Immortal Race",
+            gender_option: NoGender,
+            ..TestRace::with_stages(vec![stage])
+        }
+        .create(manager)
+    }
+
+    /// The arguments of [`Race::new`] for testing. Only the fields differing from the default need to be set.
+    pub struct TestRace {
+        pub name: &'static str,
+        pub gender_option: GenderOption,
+        pub stages: Vec<LifeStage>,
+        pub name_generator: Option<NameGenerator>,
+        pub naming_rule: NamingRule,
+        pub bonding: PairBonding,
+    }
+
+    impl Default for TestRace {
+        fn default() -> Self {
+            Self::with_stages(vec![LifeStage::simple()])
+        }
+    }
+
+    impl TestRace {
+        pub fn with_stages(stages: Vec<LifeStage>) -> Self {
+            Self {
+                name: "Race",
+                gender_option: TwoGenders,
+                stages,
+                name_generator: None,
+                naming_rule: Single,
+                bonding: PairBonding::default(),
+            }
+        }
+
+        pub fn build(self, id: usize) -> Result<Race> {
+            Race::new(
+                id,
+                self.name,
+                self.gender_option,
+                self.stages,
+                self.name_generator,
+                self.naming_rule,
+                self.bonding,
+            )
+        }
+
+        pub fn create(self, manager: &mut RaceMgr) -> RaceId {
+            manager.create(|id| self.build(id.id())).unwrap()
+        }
     }
 }
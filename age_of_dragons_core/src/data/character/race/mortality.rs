@@ -0,0 +1,24 @@
+use crate::data::character::death::DeathCause;
+use crate::data::probability::Probability;
+use serde::{Deserialize, Serialize};
+
+/// The yearly chance of members of a [`Race`](crate::data::character::race::Race) to die from a specific cause.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Mortality {
+    cause: DeathCause,
+    probability: Probability,
+}
+
+impl Mortality {
+    pub fn new(cause: DeathCause, probability: Probability) -> Self {
+        Self { cause, probability }
+    }
+
+    pub fn cause(&self) -> DeathCause {
+        self.cause
+    }
+
+    pub fn probability(&self) -> &Probability {
+        &self.probability
+    }
+}
@@ -0,0 +1,82 @@
+use crate::data::character::Character;
+use crate::data::name::Name;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// How the surnames of the members of a [`Race`](crate::data::character::race::Race) are derived from their parents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NamingRule {
+    /// Characters only have a single name.
+    #[default]
+    Single,
+    /// Characters are named after their father, like "Zura of Kalx".
+    Patronymic,
+    /// Characters are named after their mother, like "Zura of Ithra".
+    Matronymic,
+    /// Characters inherit the clan name of their father or of their mother, if the father has none.
+    /// Founders of new clans get a generated clan name.
+    Clan,
+}
+
+impl NamingRule {
+    /// Derives the surname of a child from its parents, if possible.
+    /// A single parent, that reproduced asexually, is passed as both father & mother,
+    /// so its children are named after it or inherit its clan name:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::Character;
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    ///# use age_of_dragons_core::data::character::race::naming::NamingRule::*;
+    ///# use age_of_dragons_core::data::character::race::RaceId;
+    ///# use age_of_dragons_core::data::name::Name;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let race_id = RaceId::new(0);
+    /// let mut father = Character::simple(0, race_id, Male, Date::new(0), None);
+    /// father.set_surname(Some(Name::new("Ironwing").unwrap()));
+    /// let mother = Character::simple(1, race_id, Female, Date::new(0), None);
+    ///
+    /// assert_eq!(Single.derive_surname(Some(&father), Some(&mother)).unwrap(), None);
+    /// assert_eq!(
+    ///     Patronymic.derive_surname(Some(&father), Some(&mother)).unwrap(),
+    ///     Some(Name::new("of Chacarer 0").unwrap())
+    /// );
+    /// assert_eq!(
+    ///     Matronymic.derive_surname(Some(&father), Some(&mother)).unwrap(),
+    ///     Some(Name::new("of Chacarer 1").unwrap())
+    /// );
+    /// assert_eq!(
+    ///     Clan.derive_surname(Some(&father), Some(&mother)).unwrap(),
+    ///     Some(Name::new("Ironwing").unwrap())
+    /// );
+    /// assert_eq!(Clan.derive_surname(None, Some(&mother)).unwrap(), None);
+    /// assert_eq!(
+    ///     Matronymic.derive_surname(Some(&father), Some(&father)).unwrap(),
+    ///     Some(Name::new("of Chacarer 0").unwrap())
+    /// );
+    /// ```
+    pub fn derive_surname(
+        &self,
+        father: Option<&Character>,
+        mother: Option<&Character>,
+    ) -> Result<Option<Name>> {
+        match self {
+            NamingRule::Single => Ok(None),
+            NamingRule::Patronymic => father.map(name_after).transpose(),
+            NamingRule::Matronymic => mother.map(name_after).transpose(),
+            NamingRule::Clan => Ok(father
+                .and_then(|father| father.surname())
+                .or_else(|| mother.and_then(|mother| mother.surname()))
+                .cloned()),
+        }
+    }
+
+    /// Does a [`Character`] without a derived surname get a new one?
+    pub fn founds_clan(&self) -> bool {
+        *self == NamingRule::Clan
+    }
+}
+
+fn name_after(parent: &Character) -> Result<Name> {
+    Name::new(format!("of {}", parent.name().to_str()))
+        .with_context(|| format!("Failed to name a child after {}", parent.name().to_str()))
+}
@@ -1,10 +1,45 @@
+use crate::data::probability::Probability;
+use crate::generation::number::RandomNumberGenerator;
 use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of trials of [`OffspringCount::Binomial`], which keeps its sampling fast & numerically stable.
+pub const MAX_BINOMIAL_TRIALS: u32 = 1000;
 
 /// How many offspring can a [`Race`](crate::data::character::race::Race) produce at once?
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "OffspringCountData")]
 pub enum OffspringCount {
     Fixed(u32),
-    Range { min: u32, max: u32 },
+    Range {
+        min: u32,
+        max: u32,
+    },
+    /// A table of counts & their weights, like 1 egg 60%, 2 eggs 30% & 3 eggs 10%.
+    Weighted(Vec<(u32, u32)>),
+    /// At least *min* offspring & one more for each successful trial.
+    /// Many trials with a low probability give a Poisson-like distribution with rare large clutches.
+    Binomial {
+        min: u32,
+        trials: u32,
+        probability: Probability,
+    },
+}
+
+/// The unvalidated variants of an [`OffspringCount`], which are deserialized first.
+#[derive(Deserialize)]
+enum OffspringCountData {
+    Fixed(u32),
+    Range {
+        min: u32,
+        max: u32,
+    },
+    Weighted(Vec<(u32, u32)>),
+    Binomial {
+        min: u32,
+        trials: u32,
+        probability: Probability,
+    },
 }
 
 impl OffspringCount {
@@ -48,4 +83,259 @@ impl OffspringCount {
 
         Ok(OffspringCount::Range { min, max })
     }
+
+    /// Creates a table of counts & their weights, if all are greater 0 & the total weight fits into an u32:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::count::OffspringCount;
+    /// assert!(OffspringCount::new_weighted(vec![(1, 60), (2, 30), (3, 10)]).is_ok());
+    /// assert!(OffspringCount::new_weighted(vec![]).is_err());
+    /// assert!(OffspringCount::new_weighted(vec![(0, 60)]).is_err());
+    /// assert!(OffspringCount::new_weighted(vec![(1, 0)]).is_err());
+    /// assert!(OffspringCount::new_weighted(vec![(1, u32::MAX), (2, 1)]).is_err());
+    /// ```
+    pub fn new_weighted(entries: Vec<(u32, u32)>) -> Result<OffspringCount> {
+        if entries.is_empty() {
+            bail!("The weighted offspring count needs at least one entry!");
+        } else if let Some((count, weight)) = entries
+            .iter()
+            .find(|(count, weight)| *count == 0 || *weight == 0)
+        {
+            bail!(
+                "The offspring count {} & its weight {} must be greater than 0!",
+                count,
+                weight
+            );
+        } else if entries
+            .iter()
+            .try_fold(0u32, |total, (_count, weight)| total.checked_add(*weight))
+            .is_none()
+        {
+            bail!("The total weight of the offspring counts is too large!");
+        }
+
+        Ok(OffspringCount::Weighted(entries))
+    }
+
+    /// Creates a binomial offspring count, if *min* & *trials* are greater 0
+    /// and *trials* is at most [`MAX_BINOMIAL_TRIALS`]:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::count::OffspringCount;
+    ///# use age_of_dragons_core::data::probability::Probability;
+    /// let probability = Probability::new(1, 4).unwrap();
+    ///
+    /// assert!(OffspringCount::new_binomial(1, 8, probability).is_ok());
+    /// assert!(OffspringCount::new_binomial(0, 8, probability).is_err());
+    /// assert!(OffspringCount::new_binomial(1, 0, probability).is_err());
+    /// assert!(OffspringCount::new_binomial(1, 1000, probability).is_ok());
+    /// assert!(OffspringCount::new_binomial(1, 1001, probability).is_err());
+    /// ```
+    pub fn new_binomial(min: u32, trials: u32, probability: Probability) -> Result<OffspringCount> {
+        if min == 0 {
+            bail!("The minimum must be greater than 0!");
+        } else if trials == 0 {
+            bail!("The number of trials must be greater than 0!");
+        } else if trials > MAX_BINOMIAL_TRIALS {
+            bail!(
+                "The number of trials {} must not be greater than {}!",
+                trials,
+                MAX_BINOMIAL_TRIALS
+            );
+        }
+
+        Ok(OffspringCount::Binomial {
+            min,
+            trials,
+            probability,
+        })
+    }
+
+    /// Samples the number of offspring:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::count::OffspringCount;
+    ///# use age_of_dragons_core::generation::number::RandomNumberGenerator;
+    /// let rng = RandomNumberGenerator::Mock {values: vec![0, 1, 2, 3], default: 0};
+    /// let fixed = OffspringCount::new_fixed_count(2).unwrap();
+    /// let range = OffspringCount::new_range(3, 5).unwrap();
+    ///
+    /// assert_eq!(fixed.sample(&rng, 1), 2);
+    /// assert_eq!(range.sample(&rng, 0), 3);
+    /// assert_eq!(range.sample(&rng, 1), 4);
+    /// assert_eq!(range.sample(&rng, 2), 5);
+    /// assert_eq!(range.sample(&rng, 3), 3);
+    /// ```
+    ///
+    /// Weighted counts use one random number to select an entry:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::count::OffspringCount;
+    ///# use age_of_dragons_core::generation::number::RandomNumberGenerator;
+    /// let rng = RandomNumberGenerator::Mock {values: vec![59, 60, 89, 90], default: 0};
+    /// let weighted = OffspringCount::new_weighted(vec![(1, 60), (2, 30), (3, 10)]).unwrap();
+    ///
+    /// assert_eq!(weighted.sample(&rng, 0), 1);
+    /// assert_eq!(weighted.sample(&rng, 1), 2);
+    /// assert_eq!(weighted.sample(&rng, 2), 2);
+    /// assert_eq!(weighted.sample(&rng, 3), 3);
+    /// ```
+    pub fn sample(&self, rng: &RandomNumberGenerator, index: usize) -> u32 {
+        match self {
+            OffspringCount::Fixed(count) => *count,
+            OffspringCount::Range { min, max } => min + rng.generate(index, max - min + 1),
+            OffspringCount::Weighted(entries) => sample_weighted(entries, rng, index),
+            OffspringCount::Binomial {
+                min,
+                trials,
+                probability,
+            } => min + sample_binomial(*trials, probability, rng, index),
+        }
+    }
+}
+
+impl TryFrom<OffspringCountData> for OffspringCount {
+    type Error = anyhow::Error;
+
+    fn try_from(data: OffspringCountData) -> Result<Self> {
+        match data {
+            OffspringCountData::Fixed(count) => OffspringCount::new_fixed_count(count),
+            OffspringCountData::Range { min, max } => OffspringCount::new_range(min, max),
+            OffspringCountData::Weighted(entries) => OffspringCount::new_weighted(entries),
+            OffspringCountData::Binomial {
+                min,
+                trials,
+                probability,
+            } => OffspringCount::new_binomial(min, trials, probability),
+        }
+    }
+}
+
+fn sample_weighted(entries: &[(u32, u32)], rng: &RandomNumberGenerator, index: usize) -> u32 {
+    let total = entries.iter().map(|(_count, weight)| weight).sum();
+    let mut value = rng.generate(index, total);
+
+    for (count, weight) in entries {
+        if value < *weight {
+            return *count;
+        }
+
+        value -= weight;
+    }
+
+    unreachable!("The random value is smaller than the total weight!")
+}
+
+/// Samples the number of successful trials.
+///
+/// For probabilities above 1/2 the failures are sampled instead,
+/// because the chance of no success could otherwise underflow for many trials.
+fn sample_binomial(
+    trials: u32,
+    probability: &Probability,
+    rng: &RandomNumberGenerator,
+    index: usize,
+) -> u32 {
+    let p = probability.as_f64();
+
+    if p > 0.5 {
+        trials - sample_successes(trials, 1.0 - p, rng, index)
+    } else {
+        sample_successes(trials, p, rng, index)
+    }
+}
+
+/// Samples the number of successful trials by inverting the cumulative distribution with a single random number.
+/// With at most [`MAX_BINOMIAL_TRIALS`] & a probability of at most 1/2, the chance of no success doesn't underflow.
+///
+/// Only basic floating point operations are used, so the result is the same on every platform.
+fn sample_successes(trials: u32, p: f64, rng: &RandomNumberGenerator, index: usize) -> u32 {
+    let value = rng.generate(index, u32::MAX) as f64 / u32::MAX as f64;
+    let ratio = p / (1.0 - p);
+    let mut chance = (0..trials).fold(1.0, |chance, _trial| chance * (1.0 - p));
+    let mut cumulative = chance;
+    let mut successes = 0;
+
+    while value >= cumulative && successes < trials {
+        chance *= ratio * (trials - successes) as f64 / (successes + 1) as f64;
+        successes += 1;
+        cumulative += chance;
+    }
+
+    successes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binomial_bounds() {
+        let count = OffspringCount::new_binomial(2, 6, Probability::new(1, 3).unwrap()).unwrap();
+        let low = RandomNumberGenerator::Mock {
+            values: vec![],
+            default: 0,
+        };
+        let high = RandomNumberGenerator::Mock {
+            values: vec![],
+            default: u32::MAX as u64 - 1,
+        };
+
+        assert_eq!(count.sample(&low, 0), 2);
+        assert_eq!(count.sample(&high, 0), 8);
+    }
+
+    #[test]
+    fn test_certain_binomial() {
+        let count = OffspringCount::new_binomial(1, 4, Probability::new(2, 2).unwrap()).unwrap();
+        let rng = RandomNumberGenerator::new_hash(42, 0, 0);
+
+        assert!((0..10).all(|i| count.sample(&rng, i) == 5));
+    }
+
+    #[test]
+    fn test_binomial_mean() {
+        let count = OffspringCount::new_binomial(1, 8, Probability::new(1, 4).unwrap()).unwrap();
+        let rng = RandomNumberGenerator::new_hash(42, 0, 0);
+
+        let total: u32 = (0..1000).map(|i| count.sample(&rng, i)).sum();
+
+        // the expected mean is 1 + 8 * 0.25 = 3
+        assert!((2800..3200).contains(&total), "Total is {}", total);
+    }
+
+    #[test]
+    fn test_binomial_with_many_trials() {
+        let rng = RandomNumberGenerator::new_hash(42, 0, 0);
+        let half = Probability::new(1, 2).unwrap();
+        let almost_certain = Probability::new(999, 1000).unwrap();
+
+        for (probability, expected) in [(half, 500), (almost_certain, 999)] {
+            let count = OffspringCount::new_binomial(1, MAX_BINOMIAL_TRIALS, probability).unwrap();
+
+            let samples: Vec<u32> = (0..100).map(|i| count.sample(&rng, i) - 1).collect();
+            let mean = samples.iter().sum::<u32>() / 100;
+
+            assert!(mean.abs_diff(expected) <= 10, "Mean is {}", mean);
+            assert!(samples.iter().any(|&sample| sample != samples[0]));
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let count: OffspringCount = ron::from_str("Range(min: 1, max: 3)").unwrap();
+
+        assert_eq!(count, OffspringCount::new_range(1, 3).unwrap());
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(ron::from_str::<OffspringCount>("Fixed(0)").is_err());
+        assert!(ron::from_str::<OffspringCount>("Range(min: 3, max: 1)").is_err());
+        assert!(ron::from_str::<OffspringCount>("Weighted([])").is_err());
+        assert!(ron::from_str::<OffspringCount>(
+            "Binomial(min: 1, trials: 2, probability: (threshold: 1, max: 0))"
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,150 @@
+use crate::data::time::Duration;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// How the fertility changes with the age of a [`Character`](crate::data::character::Character),
+/// as a percentage of the base probability of its [`ReproductionOption`](crate::data::character::race::reproduction::ReproductionOption).
+///
+/// The percentage is interpolated linearly between the points & stays constant before the first & after the last one.
+/// Without points the fertility is always 100%.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<FertilityPointData>", into = "Vec<FertilityPointData>")]
+pub struct FertilityCurve {
+    points: Vec<(Duration, u32)>,
+}
+
+/// An unvalidated point of a [`FertilityCurve`], which is deserialized first.
+#[derive(Serialize, Deserialize)]
+struct FertilityPointData {
+    age: Duration,
+    percentage: u32,
+}
+
+impl FertilityCurve {
+    /// Creates a fertility curve, if the ages of the points are increasing:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::fertility::FertilityCurve;
+    ///# use age_of_dragons_core::data::time::Duration;
+    /// assert!(FertilityCurve::new(vec![]).is_ok());
+    /// assert!(FertilityCurve::new(vec![(Duration::new(10), 50), (Duration::new(20), 100)]).is_ok());
+    /// assert!(FertilityCurve::new(vec![(Duration::new(20), 50), (Duration::new(20), 100)]).is_err());
+    /// assert!(FertilityCurve::new(vec![(Duration::new(20), 50), (Duration::new(10), 100)]).is_err());
+    /// ```
+    pub fn new(points: Vec<(Duration, u32)>) -> Result<Self> {
+        if let Some(window) = points.windows(2).find(|window| window[0].0 >= window[1].0) {
+            bail!(
+                "The fertility curve's age {:?} is not after {:?}!",
+                window[1].0,
+                window[0].0
+            );
+        }
+
+        Ok(Self { points })
+    }
+
+    /// Are all points between the start & the optional end of a [`LifeStage`](crate::data::character::race::stage::LifeStage)?
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::fertility::FertilityCurve;
+    ///# use age_of_dragons_core::data::time::Duration;
+    /// let curve = FertilityCurve::new(vec![(Duration::new(10), 50), (Duration::new(20), 100)]).unwrap();
+    ///
+    /// assert!(curve.is_within(Duration::new(10), Some(Duration::new(20))));
+    /// assert!(curve.is_within(Duration::new(5), None));
+    /// assert!(!curve.is_within(Duration::new(11), None));
+    /// assert!(!curve.is_within(Duration::new(5), Some(Duration::new(19))));
+    /// ```
+    pub fn is_within(&self, start: Duration, end: Option<Duration>) -> bool {
+        self.points
+            .iter()
+            .all(|(age, _percentage)| *age >= start && end.is_none_or(|end| *age <= end))
+    }
+
+    /// Returns the fertility in percent at a specific age:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::fertility::FertilityCurve;
+    ///# use age_of_dragons_core::data::time::Duration;
+    /// let curve = FertilityCurve::new(vec![
+    ///     (Duration::new(10), 50),
+    ///     (Duration::new(20), 100),
+    ///     (Duration::new(40), 0),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(FertilityCurve::default().get_percentage(Duration::new(5)), 100);
+    /// assert_eq!(curve.get_percentage(Duration::new(5)), 50);
+    /// assert_eq!(curve.get_percentage(Duration::new(15)), 75);
+    /// assert_eq!(curve.get_percentage(Duration::new(20)), 100);
+    /// assert_eq!(curve.get_percentage(Duration::new(35)), 25);
+    /// assert_eq!(curve.get_percentage(Duration::new(50)), 0);
+    /// ```
+    pub fn get_percentage(&self, age: Duration) -> u32 {
+        let index = self
+            .points
+            .partition_point(|(point_age, _)| *point_age <= age);
+
+        match (index.checked_sub(1), self.points.get(index)) {
+            (None, None) => 100,
+            (None, Some((_age, percentage))) => *percentage,
+            (Some(previous), None) => self.points[previous].1,
+            (Some(previous), Some(&(next_age, next_percentage))) => {
+                let (previous_age, previous_percentage) = self.points[previous];
+                let elapsed = (age - previous_age).total_months() as i64;
+                let length = (next_age - previous_age).total_months() as i64;
+                let difference = next_percentage as i64 - previous_percentage as i64;
+
+                (previous_percentage as i64 + difference * elapsed / length) as u32
+            }
+        }
+    }
+}
+
+impl TryFrom<Vec<FertilityPointData>> for FertilityCurve {
+    type Error = anyhow::Error;
+
+    fn try_from(data: Vec<FertilityPointData>) -> Result<Self> {
+        FertilityCurve::new(
+            data.into_iter()
+                .map(|point| (point.age, point.percentage))
+                .collect(),
+        )
+    }
+}
+
+impl From<FertilityCurve> for Vec<FertilityPointData> {
+    fn from(curve: FertilityCurve) -> Self {
+        curve
+            .points
+            .into_iter()
+            .map(|(age, percentage)| FertilityPointData { age, percentage })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let points = vec![(Duration::new(1), 50), (Duration::new(2), 100)];
+        let curve = FertilityCurve::new(points).unwrap();
+        let string = ron::to_string(&curve).unwrap();
+
+        assert_eq!(ron::from_str::<FertilityCurve>(&string).unwrap(), curve);
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let load = |string| ron::from_str::<FertilityCurve>(string);
+
+        assert!(
+            load("[(age: (years: 1), percentage: 50), (age: (years: 2), percentage: 100)]").is_ok()
+        );
+        assert!(
+            load("[(age: (years: 2), percentage: 50), (age: (years: 1), percentage: 100)]")
+                .is_err()
+        );
+    }
+}
@@ -1,20 +1,83 @@
 use crate::data::character::race::reproduction::count::OffspringCount;
+use crate::data::character::race::reproduction::fertility::FertilityCurve;
 use crate::data::probability::Probability;
+use crate::data::time::Duration;
+use serde::{Deserialize, Serialize};
 
 pub mod count;
+pub mod fertility;
 
 /// How can a [`Race`](crate::data::character::race::Race) produce?
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ReproductionOption {
     probability: Probability,
     offspring_count: OffspringCount,
+    /// Scales the probability based on the age.
+    #[serde(default)]
+    fertility: FertilityCurve,
+    /// How long after giving birth a [`Character`](crate::data::character::Character) can't become pregnant again.
+    #[serde(default)]
+    cooldown: Duration,
 }
 
 impl ReproductionOption {
-    pub fn new(probability: Probability, offspring_count: OffspringCount) -> ReproductionOption {
+    pub fn new(
+        probability: Probability,
+        offspring_count: OffspringCount,
+        fertility: FertilityCurve,
+        cooldown: Duration,
+    ) -> ReproductionOption {
         ReproductionOption {
             probability,
             offspring_count,
+            fertility,
+            cooldown,
         }
     }
+
+    /// A simple way to create a reproduction option with constant fertility & without cooldown.
+    pub fn simple(probability: Probability, offspring_count: OffspringCount) -> ReproductionOption {
+        Self::new(
+            probability,
+            offspring_count,
+            FertilityCurve::default(),
+            Duration::default(),
+        )
+    }
+
+    pub fn probability(&self) -> &Probability {
+        &self.probability
+    }
+
+    pub fn offspring_count(&self) -> &OffspringCount {
+        &self.offspring_count
+    }
+
+    pub fn fertility(&self) -> &FertilityCurve {
+        &self.fertility
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// Returns the probability of becoming pregnant at a specific age, if possible:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::reproduction::count::OffspringCount;
+    ///# use age_of_dragons_core::data::character::race::reproduction::fertility::FertilityCurve;
+    ///# use age_of_dragons_core::data::character::race::reproduction::ReproductionOption;
+    ///# use age_of_dragons_core::data::probability::Probability;
+    ///# use age_of_dragons_core::data::time::Duration;
+    /// let probability = Probability::new(1, 4).unwrap();
+    /// let count = OffspringCount::new_fixed_count(1).unwrap();
+    /// let curve = FertilityCurve::new(vec![(Duration::new(10), 50), (Duration::new(20), 0)]).unwrap();
+    /// let reproduction = ReproductionOption::new(probability, count, curve, Duration::default());
+    ///
+    /// assert_eq!(reproduction.get_probability(Duration::new(10)), Probability::new(50, 400).ok());
+    /// assert_eq!(reproduction.get_probability(Duration::new(20)), None);
+    /// ```
+    pub fn get_probability(&self, age: Duration) -> Option<Probability> {
+        self.probability.scale(self.fertility.get_percentage(age))
+    }
 }
@@ -1,10 +1,12 @@
+use crate::data::character::race::mortality::Mortality;
 use crate::data::character::race::reproduction::ReproductionOption;
 use crate::data::name::Name;
 use crate::data::time::Duration;
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
 /// The id of a [`LifeStage`] for a specific [`Race`](crate::data::character::race::Race).
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct LifeStageId(usize);
 
 impl LifeStageId {
@@ -22,13 +24,26 @@ impl LifeStageId {
 }
 
 /// Members of most [`Races`](crate::data::character::race::Race) go through multiple life stages while growing up.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "LifeStageData")]
 pub struct LifeStage {
     name: Name,
     id: LifeStageId,
     /// The life stage lasts forever, if it has no max age.
     max_age: Option<Duration>,
     reproduction: Option<ReproductionOption>,
+    /// The yearly chances to die from causes other than old age.
+    mortality: Vec<Mortality>,
+}
+
+/// The unvalidated fields of a [`LifeStage`], which are deserialized first.
+#[derive(Deserialize)]
+struct LifeStageData {
+    name: Name,
+    id: LifeStageId,
+    max_age: Option<Duration>,
+    reproduction: Option<ReproductionOption>,
+    mortality: Vec<Mortality>,
 }
 
 impl LifeStage {
@@ -38,6 +53,7 @@ impl LifeStage {
         index: usize,
         max_age: Option<Duration>,
         reproduction: Option<ReproductionOption>,
+        mortality: Vec<Mortality>,
     ) -> Result<Self> {
         let name = name.into();
         let name =
@@ -48,6 +64,7 @@ impl LifeStage {
             id: LifeStageId::new(index),
             max_age,
             reproduction,
+            mortality,
         })
     }
 
@@ -58,6 +75,7 @@ impl LifeStage {
             id: LifeStageId::new(0),
             max_age: None,
             reproduction: None,
+            mortality: Vec::new(),
         }
     }
 
@@ -76,4 +94,22 @@ impl LifeStage {
     pub fn reproduction(&self) -> &Option<ReproductionOption> {
         &self.reproduction
     }
+
+    pub fn mortality(&self) -> &[Mortality] {
+        &self.mortality
+    }
+}
+
+impl TryFrom<LifeStageData> for LifeStage {
+    type Error = anyhow::Error;
+
+    fn try_from(data: LifeStageData) -> Result<Self> {
+        LifeStage::new(
+            data.name,
+            data.id.index(),
+            data.max_age,
+            data.reproduction,
+            data.mortality,
+        )
+    }
 }
@@ -0,0 +1,261 @@
+use crate::data::character::relation::manager::CharacterRelationMgr;
+use crate::data::character::relation::CharacterRelationType;
+use crate::data::character::relation::CharacterRelationType::{Child, Parent};
+use crate::data::character::CharacterId;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Returns the parents of a [`Character`](crate::data::character::Character).
+pub fn get_parents(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    get_targets(manager, id, Parent)
+}
+
+/// Returns the children of a [`Character`](crate::data::character::Character).
+pub fn get_children(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    get_targets(manager, id, Child)
+}
+
+/// Returns all ancestors of a [`Character`](crate::data::character::Character),
+/// starting with the parents & then going back one generation at a time.
+pub fn get_ancestors(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    collect_generations(manager, id, Parent)
+}
+
+/// Returns all descendants of a [`Character`](crate::data::character::Character),
+/// starting with the children & then going forward one generation at a time.
+pub fn get_descendants(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    collect_generations(manager, id, Child)
+}
+
+/// Returns the siblings of a [`Character`](crate::data::character::Character), who share all the parents.
+pub fn get_siblings(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    let parents = to_set(get_parents(manager, id));
+
+    get_children_of_parents(manager, id)
+        .into_iter()
+        .filter(|other| to_set(get_parents(manager, *other)) == parents)
+        .collect()
+}
+
+/// Returns the half-siblings of a [`Character`](crate::data::character::Character),
+/// who share some, but not all of the parents.
+pub fn get_half_siblings(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    let parents = to_set(get_parents(manager, id));
+
+    get_children_of_parents(manager, id)
+        .into_iter()
+        .filter(|other| to_set(get_parents(manager, *other)) != parents)
+        .collect()
+}
+
+/// Finds the shortest path of [`Parent`] & [`Child`] relations between 2 [`Characters`](crate::data::character::Character).
+/// The path includes both characters & is [`None`], if they are not related by blood.
+///
+/// ```
+///# use age_of_dragons_core::data::character::CharacterId;
+///# use age_of_dragons_core::data::character::relation::CharacterRelationType::Child;
+///# use age_of_dragons_core::data::character::relation::genealogy::find_kinship_path;
+///# use age_of_dragons_core::data::character::relation::manager::CharacterRelationMgr;
+///# use age_of_dragons_core::data::time::Date;
+/// let parent = CharacterId::new(0);
+/// let child0 = CharacterId::new(1);
+/// let child1 = CharacterId::new(2);
+/// let stranger = CharacterId::new(3);
+/// let mut manager = CharacterRelationMgr::default();
+/// manager.add_relation_between(parent, child0, Child, Date::new(0));
+/// manager.add_relation_between(parent, child1, Child, Date::new(0));
+///
+/// assert_eq!(find_kinship_path(&manager, child0, child0), Some(vec![child0]));
+/// assert_eq!(find_kinship_path(&manager, child0, child1), Some(vec![child0, parent, child1]));
+/// assert_eq!(find_kinship_path(&manager, child0, stranger), None);
+/// ```
+pub fn find_kinship_path(
+    manager: &CharacterRelationMgr,
+    start: CharacterId,
+    end: CharacterId,
+) -> Option<Vec<CharacterId>> {
+    let mut previous: HashMap<CharacterId, CharacterId> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    previous.insert(start, start);
+
+    while let Some(current) = queue.pop_front() {
+        if current == end {
+            let mut path = vec![end];
+            let mut step = end;
+
+            while step != start {
+                step = previous[&step];
+                path.push(step);
+            }
+
+            path.reverse();
+            return Some(path);
+        }
+
+        for relation in manager.get_relations_of(current) {
+            let is_blood = matches!(relation.relation_type(), Parent | Child);
+
+            if is_blood && !previous.contains_key(&relation.target()) {
+                previous.insert(relation.target(), current);
+                queue.push_back(relation.target());
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns all other children of the [`Character`](crate::data::character::Character)'s parents.
+fn get_children_of_parents(manager: &CharacterRelationMgr, id: CharacterId) -> Vec<CharacterId> {
+    let mut result = Vec::new();
+
+    for parent in get_parents(manager, id) {
+        for child in get_children(manager, parent) {
+            if child != id && !result.contains(&child) {
+                result.push(child);
+            }
+        }
+    }
+
+    result
+}
+
+/// Collects the targets of a specific relation type recursively.
+fn collect_generations(
+    manager: &CharacterRelationMgr,
+    id: CharacterId,
+    relation_type: CharacterRelationType,
+) -> Vec<CharacterId> {
+    let mut result = Vec::new();
+    let mut visited = HashSet::from([id]);
+    let mut queue = VecDeque::from([id]);
+
+    while let Some(current) = queue.pop_front() {
+        for target in get_targets(manager, current, relation_type) {
+            if visited.insert(target) {
+                result.push(target);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    result
+}
+
+fn get_targets(
+    manager: &CharacterRelationMgr,
+    id: CharacterId,
+    relation_type: CharacterRelationType,
+) -> Vec<CharacterId> {
+    manager
+        .get_relations_of(id)
+        .iter()
+        .filter(|relation| relation.relation_type() == relation_type)
+        .map(|relation| relation.target())
+        .collect()
+}
+
+fn to_set(ids: Vec<CharacterId>) -> HashSet<CharacterId> {
+    ids.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::relation::CharacterRelationType::Mate;
+    use crate::data::time::Date;
+
+    //  0 + 1   2
+    //    |   /   \
+    //    3 + 4    5
+    //      |
+    //      6
+    fn init_family() -> (CharacterRelationMgr, Vec<CharacterId>) {
+        let ids: Vec<CharacterId> = (0..8).map(CharacterId::new).collect();
+        let mut manager = CharacterRelationMgr::default();
+
+        manager.add_relation_between(ids[0], ids[1], Mate, Date::new(0));
+        manager.add_relation_between(ids[0], ids[3], Child, Date::new(0));
+        manager.add_relation_between(ids[1], ids[3], Child, Date::new(0));
+        manager.add_relation_between(ids[2], ids[4], Child, Date::new(0));
+        manager.add_relation_between(ids[2], ids[5], Child, Date::new(0));
+        manager.add_relation_between(ids[3], ids[4], Mate, Date::new(0));
+        manager.add_relation_between(ids[3], ids[6], Child, Date::new(0));
+        manager.add_relation_between(ids[4], ids[6], Child, Date::new(0));
+
+        (manager, ids)
+    }
+
+    #[test]
+    fn test_parents_and_children() {
+        let (manager, ids) = init_family();
+
+        assert_eq!(get_parents(&manager, ids[6]), vec![ids[3], ids[4]]);
+        assert_eq!(get_children(&manager, ids[2]), vec![ids[4], ids[5]]);
+        assert!(get_parents(&manager, ids[0]).is_empty());
+        assert!(get_children(&manager, ids[6]).is_empty());
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let (manager, ids) = init_family();
+
+        assert_eq!(
+            get_ancestors(&manager, ids[6]),
+            vec![ids[3], ids[4], ids[0], ids[1], ids[2]]
+        );
+        assert!(get_ancestors(&manager, ids[2]).is_empty());
+    }
+
+    #[test]
+    fn test_descendants() {
+        let (manager, ids) = init_family();
+
+        assert_eq!(get_descendants(&manager, ids[0]), vec![ids[3], ids[6]]);
+        assert_eq!(
+            get_descendants(&manager, ids[2]),
+            vec![ids[4], ids[5], ids[6]]
+        );
+        assert!(get_descendants(&manager, ids[6]).is_empty());
+    }
+
+    #[test]
+    fn test_siblings() {
+        let (mut manager, ids) = init_family();
+        manager.add_relation_between(ids[3], ids[7], Child, Date::new(0));
+        manager.add_relation_between(ids[4], ids[7], Child, Date::new(0));
+
+        assert_eq!(get_siblings(&manager, ids[6]), vec![ids[7]]);
+        assert!(get_half_siblings(&manager, ids[6]).is_empty());
+    }
+
+    #[test]
+    fn test_half_siblings() {
+        let (mut manager, ids) = init_family();
+        manager.add_relation_between(ids[7], ids[5], Child, Date::new(0));
+
+        assert!(get_siblings(&manager, ids[4]).is_empty());
+        assert_eq!(get_half_siblings(&manager, ids[4]), vec![ids[5]]);
+        assert_eq!(get_half_siblings(&manager, ids[5]), vec![ids[4]]);
+    }
+
+    #[test]
+    fn test_kinship_path() {
+        let (manager, ids) = init_family();
+
+        assert_eq!(
+            find_kinship_path(&manager, ids[5], ids[0]),
+            Some(vec![ids[5], ids[2], ids[4], ids[6], ids[3], ids[0]])
+        );
+        assert_eq!(find_kinship_path(&manager, ids[0], ids[7]), None);
+    }
+
+    #[test]
+    fn test_mates_are_not_kin() {
+        let mut manager = CharacterRelationMgr::default();
+        let id0 = CharacterId::new(0);
+        let id1 = CharacterId::new(1);
+        manager.add_relation_between(id0, id1, Mate, Date::new(0));
+
+        assert_eq!(find_kinship_path(&manager, id0, id1), None);
+    }
+}
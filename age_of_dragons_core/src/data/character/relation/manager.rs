@@ -1,15 +1,36 @@
-use crate::data::character::relation::{CharacterRelation, CharacterRelationType};
+use crate::data::character::relation::{CharacterRelation, CharacterRelationType, RelationEnd};
 use crate::data::character::CharacterId;
+use crate::data::storage::Storage;
+use crate::data::time::Date;
+use serde::{Deserialize, Serialize};
 
 /// Stores the [`relations`](CharacterRelation) between all [`Characters`](crate::data::character::Character).
-#[derive(Default, Debug)]
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CharacterRelationMgr {
-    relations: Vec<Vec<CharacterRelation>>,
+    relations: Storage<CharacterId, Vec<CharacterRelation>>,
+    #[serde(skip)]
     no_relations: Vec<CharacterRelation>,
 }
 
 impl CharacterRelationMgr {
-    /// Returns all the [`relations`](CharacterRelation) between 2 [`Characters`](crate::data::character::Character).
+    /// Adds a [`relation`](CharacterRelation) between 2 [`Characters`](crate::data::character::Character), which starts at *date*.
+    /// The second character is the *relation_type* of the first one,
+    /// and the first character is the reversed type of the second one.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::CharacterId;
+    ///# use age_of_dragons_core::data::character::relation::CharacterRelationType::*;
+    ///# use age_of_dragons_core::data::character::relation::manager::CharacterRelationMgr;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let parent = CharacterId::new(0);
+    /// let child = CharacterId::new(1);
+    /// let mut manager = CharacterRelationMgr::default();
+    ///
+    /// manager.add_relation_between(parent, child, Child, Date::new(3));
+    ///
+    /// assert_eq!(manager.get_relations_between(parent, child), vec![Child]);
+    /// assert_eq!(manager.get_relations_between(child, parent), vec![Parent]);
+    /// ```
     ///
     /// # Panic
     ///
@@ -19,19 +40,20 @@ impl CharacterRelationMgr {
     ///# use age_of_dragons_core::data::character::CharacterId;
     ///# use age_of_dragons_core::data::character::relation::CharacterRelationType::Mate;
     ///# use age_of_dragons_core::data::character::relation::manager::CharacterRelationMgr;
+    ///# use age_of_dragons_core::data::time::Date;
     /// let id = CharacterId::new(0);
-    /// CharacterRelationMgr::default().add_relation_between(id, id, Mate);
+    /// CharacterRelationMgr::default().add_relation_between(id, id, Mate, Date::new(0));
     /// ```
     pub fn add_relation_between(
         &mut self,
         id0: CharacterId,
         id1: CharacterId,
         relation_type: CharacterRelationType,
+        date: Date,
     ) {
         assert_ne!(id0, id1, "Character can't have a relation with itself!");
-        self.check_size_for_both(id0, id1);
-        self.add_relation(id0, id1, relation_type);
-        self.add_relation(id1, id0, relation_type);
+        self.add_relation(id0, id1, relation_type, date);
+        self.add_relation(id1, id0, relation_type.reverse(), date);
     }
 
     fn add_relation(
@@ -39,15 +61,90 @@ impl CharacterRelationMgr {
         id0: CharacterId,
         id1: CharacterId,
         relation_type: CharacterRelationType,
+        start: Date,
     ) {
-        if let Some(relations) = self.relations.get_mut(id0.id()) {
-            relations.push(CharacterRelation {
+        self.relations
+            .get_mut_or_default(id0)
+            .push(CharacterRelation {
                 id: id1,
                 relation_type,
+                start,
+                end: None,
             })
+    }
+
+    /// Ends the active [`relation`](CharacterRelation) of a specific [`type`](CharacterRelationType) between 2 [`Characters`](crate::data::character::Character) on both sides.
+    /// The ended relation is kept, so the old relations are still known.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::CharacterId;
+    ///# use age_of_dragons_core::data::character::relation::CharacterRelationType::Mate;
+    ///# use age_of_dragons_core::data::character::relation::RelationEnd;
+    ///# use age_of_dragons_core::data::character::relation::RelationEndReason::Separation;
+    ///# use age_of_dragons_core::data::character::relation::manager::CharacterRelationMgr;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let id0 = CharacterId::new(0);
+    /// let id1 = CharacterId::new(1);
+    /// let end = RelationEnd::new(Date::new(5), Separation);
+    /// let mut manager = CharacterRelationMgr::default();
+    /// manager.add_relation_between(id0, id1, Mate, Date::new(2));
+    ///
+    /// manager.end_relation_between(id0, id1, Mate, end);
+    ///
+    /// assert!(!manager.has_active_relation(id0, Mate));
+    /// assert!(!manager.has_active_relation(id1, Mate));
+    /// assert_eq!(manager.get_relations_of(id1)[0].end(), Some(&end));
+    /// assert_eq!(manager.get_relations_between(id0, id1), vec![Mate]);
+    /// ```
+    pub fn end_relation_between(
+        &mut self,
+        id0: CharacterId,
+        id1: CharacterId,
+        relation_type: CharacterRelationType,
+        end: RelationEnd,
+    ) {
+        self.end_relation(id0, id1, relation_type, end);
+        self.end_relation(id1, id0, relation_type.reverse(), end);
+    }
+
+    fn end_relation(
+        &mut self,
+        id0: CharacterId,
+        id1: CharacterId,
+        relation_type: CharacterRelationType,
+        end: RelationEnd,
+    ) {
+        if let Some(relation) = self.relations.get_mut(id0).and_then(|relations| {
+            relations.iter_mut().find(|relation| {
+                relation.id == id1
+                    && relation.relation_type == relation_type
+                    && relation.is_active()
+            })
+        }) {
+            relation.end = Some(end);
         }
     }
 
+    /// Ends all active [`relations`](CharacterRelation) of a specific [`type`](CharacterRelationType) of a [`Character`](crate::data::character::Character).
+    /// Returns the other characters of the ended relations.
+    pub fn end_relations_of(
+        &mut self,
+        id: CharacterId,
+        relation_type: CharacterRelationType,
+        end: RelationEnd,
+    ) -> Vec<CharacterId> {
+        let targets: Vec<CharacterId> = self
+            .get_active_relations_of(id, relation_type)
+            .map(|relation| relation.id)
+            .collect();
+
+        for &target in &targets {
+            self.end_relation_between(id, target, relation_type, end);
+        }
+
+        targets
+    }
+
     /// Returns all [`relations`](CharacterRelation) between 2 [`Characters`](crate::data::character::Character).
     pub fn get_relations_between(
         &self,
@@ -55,7 +152,7 @@ impl CharacterRelationMgr {
         id1: CharacterId,
     ) -> Vec<CharacterRelationType> {
         self.relations
-            .get(id0.id())
+            .get(id0)
             .map(|relations| {
                 relations
                     .iter()
@@ -68,48 +165,44 @@ impl CharacterRelationMgr {
 
     /// Returns all [`relations`](CharacterRelation) of a [`Character`](crate::data::character::Character).
     pub fn get_relations_of(&self, id: CharacterId) -> &[CharacterRelation] {
-        self.relations.get(id.id()).unwrap_or(&self.no_relations)
-    }
-
-    /// Has the [`character`](crate::data::character::Character) a [`relation`](CharacterRelation) of a specific [`type`](CharacterRelationType)?
-    pub fn has_relation(&self, id: CharacterId, relation_type: CharacterRelationType) -> bool {
-        self.relations
-            .get(id.id())
-            .filter(|relations| {
-                relations
-                    .iter()
-                    .any(|relation| relation.relation_type == relation_type)
-            })
-            .is_some()
+        self.relations.get(id).unwrap_or(&self.no_relations)
     }
 
-    /// Makes sure that the vector of relations is large enough to contain both characters.
-    fn check_size_for_both(&mut self, id0: CharacterId, id1: CharacterId) {
-        self.check_size(id0.0.max(id1.0));
+    /// Returns the active [`relations`](CharacterRelation) of a specific [`type`](CharacterRelationType) of a [`Character`](crate::data::character::Character).
+    pub fn get_active_relations_of(
+        &self,
+        id: CharacterId,
+        relation_type: CharacterRelationType,
+    ) -> impl Iterator<Item = &CharacterRelation> {
+        self.get_relations_of(id)
+            .iter()
+            .filter(move |relation| relation.relation_type == relation_type && relation.is_active())
     }
 
-    /// Makes sure that the vector of relations is large enough to contain the character.
-    fn check_size(&mut self, min_size: usize) {
-        if self.relations.len() <= min_size {
-            let missing_characters = (min_size - self.relations.len()) + 1;
-
-            for _i in 0..missing_characters {
-                self.relations.push(Vec::new());
-            }
-        }
+    /// Has the [`character`](crate::data::character::Character) an active [`relation`](CharacterRelation) of a specific [`type`](CharacterRelationType)?
+    pub fn has_active_relation(
+        &self,
+        id: CharacterId,
+        relation_type: CharacterRelationType,
+    ) -> bool {
+        self.get_active_relations_of(id, relation_type)
+            .next()
+            .is_some()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::character::relation::CharacterRelationType::{Child, Mate};
+    use crate::data::character::relation::RelationEndReason::{Death, Separation};
 
     #[test]
     fn test_get_relations_with_unknown_id() {
         let mut manager = CharacterRelationMgr::default();
         let id0 = CharacterId::new(0);
         let id1 = CharacterId::new(1);
-        manager.check_size(0);
+        manager.relations.get_mut_or_default(id0);
 
         assert!(manager.get_relations_between(id0, id1).is_empty());
         assert!(manager.get_relations_between(id1, id0).is_empty());
@@ -117,4 +210,41 @@ mod tests {
         assert!(manager.get_relations_of(id0).is_empty());
         assert!(manager.get_relations_of(id1).is_empty());
     }
+
+    #[test]
+    fn test_end_relations_of() {
+        let mut manager = CharacterRelationMgr::default();
+        let ids: Vec<CharacterId> = (0..4).map(CharacterId::new).collect();
+        let end = RelationEnd::new(Date::new(7), Death);
+        manager.add_relation_between(ids[0], ids[1], Mate, Date::new(1));
+        manager.add_relation_between(ids[0], ids[2], Mate, Date::new(2));
+        manager.add_relation_between(ids[0], ids[3], Child, Date::new(3));
+
+        assert_eq!(manager.end_relations_of(ids[0], Mate, end), ids[1..3]);
+
+        assert!(!manager.has_active_relation(ids[0], Mate));
+        assert!(!manager.has_active_relation(ids[1], Mate));
+        assert!(!manager.has_active_relation(ids[2], Mate));
+        assert!(manager.has_active_relation(ids[0], Child));
+        assert!(manager.end_relations_of(ids[0], Mate, end).is_empty());
+    }
+
+    #[test]
+    fn test_new_relation_after_end() {
+        let mut manager = CharacterRelationMgr::default();
+        let id0 = CharacterId::new(0);
+        let id1 = CharacterId::new(1);
+        let id2 = CharacterId::new(2);
+        manager.add_relation_between(id0, id1, Mate, Date::new(1));
+        manager.end_relation_between(id0, id1, Mate, RelationEnd::new(Date::new(4), Separation));
+        manager.add_relation_between(id0, id2, Mate, Date::new(6));
+
+        let active: Vec<CharacterId> = manager
+            .get_active_relations_of(id0, Mate)
+            .map(|relation| relation.target())
+            .collect();
+        assert_eq!(active, vec![id2]);
+        assert_eq!(manager.get_relations_of(id0).len(), 2);
+        assert_eq!(manager.get_relations_of(id2)[0].start(), Date::new(6));
+    }
 }
@@ -1,18 +1,76 @@
 use crate::data::character::CharacterId;
+use crate::data::time::Date;
+use serde::{Deserialize, Serialize};
 
+pub mod genealogy;
 pub mod manager;
 
 /// The type of a [`relationship`](CharacterRelation) between 2 [`Characters`](crate::data::character::Character).
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum CharacterRelationType {
+    /// The target is the child of the [`Character`](crate::data::character::Character).
+    Child,
     Mate,
+    /// The target is the parent of the [`Character`](crate::data::character::Character).
+    Parent,
+}
+
+impl CharacterRelationType {
+    /// Returns the type of the same relation from the other side.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::relation::CharacterRelationType::*;
+    /// assert_eq!(Child.reverse(), Parent);
+    /// assert_eq!(Mate.reverse(), Mate);
+    /// assert_eq!(Parent.reverse(), Child);
+    /// ```
+    pub fn reverse(&self) -> Self {
+        match self {
+            CharacterRelationType::Child => CharacterRelationType::Parent,
+            CharacterRelationType::Mate => CharacterRelationType::Mate,
+            CharacterRelationType::Parent => CharacterRelationType::Child,
+        }
+    }
+}
+
+/// Why a [`relationship`](CharacterRelation) ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RelationEndReason {
+    /// One of the 2 [`Characters`](crate::data::character::Character) died.
+    Death,
+    /// The 2 [`Characters`](crate::data::character::Character) separated.
+    Separation,
+}
+
+/// The end of a [`relationship`](CharacterRelation).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RelationEnd {
+    date: Date,
+    reason: RelationEndReason,
+}
+
+impl RelationEnd {
+    pub fn new(date: Date, reason: RelationEndReason) -> Self {
+        Self { date, reason }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn reason(&self) -> RelationEndReason {
+        self.reason
+    }
 }
 
 /// A relationship between 2 [`Characters`](crate::data::character::Character).
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// It starts at a specific date & can end, which keeps it in the history.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct CharacterRelation {
     id: CharacterId,
     relation_type: CharacterRelationType,
+    start: Date,
+    end: Option<RelationEnd>,
 }
 
 impl CharacterRelation {
@@ -23,4 +81,17 @@ impl CharacterRelation {
     pub fn relation_type(&self) -> CharacterRelationType {
         self.relation_type
     }
+
+    pub fn start(&self) -> Date {
+        self.start
+    }
+
+    pub fn end(&self) -> Option<&RelationEnd> {
+        self.end.as_ref()
+    }
+
+    /// Has the relationship not ended yet?
+    pub fn is_active(&self) -> bool {
+        self.end.is_none()
+    }
 }
@@ -0,0 +1,210 @@
+use crate::data::character::CharacterId;
+use crate::data::event::{Event, EventKind, EventType};
+use crate::data::time::Date;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Stores all [`events`](Event) in the order they happened.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "EventHistoryData")]
+pub struct EventHistory {
+    events: Vec<Event>,
+}
+
+/// The unvalidated fields of an [`EventHistory`], which are deserialized first.
+#[derive(Deserialize)]
+struct EventHistoryData {
+    events: Vec<Event>,
+}
+
+impl EventHistory {
+    /// Adds an [`Event`] after all events, that didn't happen later:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::CharacterId;
+    ///# use age_of_dragons_core::data::character::death::DeathCause::OldAge;
+    ///# use age_of_dragons_core::data::event::{Event, EventType};
+    ///# use age_of_dragons_core::data::event::history::EventHistory;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let event0 = EventType::Died(CharacterId::new(0), OldAge);
+    /// let event1 = EventType::Died(CharacterId::new(1), OldAge);
+    /// let mut history = EventHistory::default();
+    ///
+    /// history.add(Date::new(10), event0.clone());
+    /// history.add(Date::new(9), event1.clone());
+    ///
+    /// assert_eq!(history.get_all(), &[Event::new(Date::new(9), event1), Event::new(Date::new(10), event0)]);
+    /// ```
+    pub fn add(&mut self, date: Date, event_type: EventType) {
+        let index = self.events.partition_point(|e| e.date() <= date);
+
+        self.events.insert(index, Event::new(date, event_type));
+    }
+
+    pub fn get_all(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Returns all [`events`](Event) involving a [`Character`](crate::data::character::Character).
+    pub fn get_events_of(&self, id: CharacterId) -> Vec<&Event> {
+        self.events.iter().filter(|e| e.involves(id)).collect()
+    }
+
+    /// Returns all [`events`](Event) of a specific [`kind`](EventKind).
+    pub fn get_events_of_kind(&self, kind: EventKind) -> Vec<&Event> {
+        self.events
+            .iter()
+            .filter(|e| e.event_type().kind() == kind)
+            .collect()
+    }
+
+    /// Returns all [`events`](Event) between 2 dates, including both.
+    pub fn get_events_between(&self, start: Date, end: Date) -> &[Event] {
+        let start_index = self.events.partition_point(|e| e.date() < start);
+        let end_index = self.events.partition_point(|e| e.date() <= end);
+
+        if start_index < end_index {
+            &self.events[start_index..end_index]
+        } else {
+            &[]
+        }
+    }
+}
+
+impl TryFrom<EventHistoryData> for EventHistory {
+    type Error = anyhow::Error;
+
+    fn try_from(data: EventHistoryData) -> Result<Self> {
+        if let Some(window) = data
+            .events
+            .windows(2)
+            .find(|window| window[0].date() > window[1].date())
+        {
+            bail!(
+                "The event at {:?} happened before the previous one at {:?}!",
+                window[1].date(),
+                window[0].date()
+            );
+        }
+
+        Ok(Self {
+            events: data.events,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::death::DeathCause::Accident;
+    use crate::data::character::race::stage::LifeStageId;
+    use EventType::*;
+
+    #[test]
+    fn test_get_events_of() {
+        let (history, id0, id1, id2) = init_history();
+
+        assert_eq!(history.get_events_of(id0).len(), 3);
+        assert_eq!(history.get_events_of(id1).len(), 2);
+        assert_eq!(history.get_events_of(id2).len(), 2);
+        assert!(history.get_events_of(CharacterId::new(3)).is_empty());
+    }
+
+    #[test]
+    fn test_get_events_of_kind() {
+        let (history, _id0, _id1, id2) = init_history();
+
+        assert_eq!(history.get_events_of_kind(EventKind::BecameMates).len(), 1);
+        assert_eq!(
+            history.get_events_of_kind(EventKind::Died),
+            vec![&Event::new(Date::new(30), Died(id2, Accident))]
+        );
+    }
+
+    #[test]
+    fn test_get_events_between() {
+        let (history, _id0, _id1, _id2) = init_history();
+
+        assert_eq!(
+            history
+                .get_events_between(Date::new(0), Date::new(50))
+                .len(),
+            4
+        );
+        assert_eq!(
+            history
+                .get_events_between(Date::new(10), Date::new(20))
+                .len(),
+            3
+        );
+        assert_eq!(
+            history
+                .get_events_between(Date::new(11), Date::new(29))
+                .len(),
+            1
+        );
+        assert_eq!(
+            history
+                .get_events_between(Date::new(21), Date::new(29))
+                .len(),
+            0
+        );
+        assert_eq!(
+            history
+                .get_events_between(Date::new(30), Date::new(10))
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_add_keeps_order_of_same_date() {
+        let (mut history, id0, id1, _id2) = init_history();
+
+        history.add(Date::new(10), Separated(id0, id1));
+
+        let events = history.get_events_between(Date::new(10), Date::new(10));
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].event_type(), &Separated(id0, id1));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let (history, _id0, _id1, _id2) = init_history();
+        let string = ron::to_string(&history).unwrap();
+
+        assert_eq!(ron::from_str::<EventHistory>(&string).unwrap(), history);
+    }
+
+    #[test]
+    fn test_deserialize_unsorted() {
+        let (history, _id0, _id1, _id2) = init_history();
+        let string = ron::to_string(&history).unwrap();
+        // the last event happens first
+        let unsorted = string.replacen("(360)", "(0)", 1);
+
+        assert_ne!(string, unsorted);
+        assert!(ron::from_str::<EventHistory>(&unsorted).is_err());
+    }
+
+    fn init_history() -> (EventHistory, CharacterId, CharacterId, CharacterId) {
+        let id0 = CharacterId::new(0);
+        let id1 = CharacterId::new(1);
+        let id2 = CharacterId::new(2);
+        let mut history = EventHistory::default();
+
+        history.add(Date::new(10), ChangedLifeStage(id0, LifeStageId::new(1)));
+        history.add(Date::new(10), BecameMates(id0, id1));
+        history.add(
+            Date::new(20),
+            WasBorn {
+                character: id2,
+                mother: id0,
+                father: id1,
+            },
+        );
+        history.add(Date::new(30), Died(id2, Accident));
+
+        (history, id0, id1, id2)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::data::character::death::DeathCause;
+use crate::data::character::gender::Gender;
+use crate::data::character::race::stage::LifeStageId;
+use crate::data::character::CharacterId;
+use crate::data::time::Date;
+use serde::{Deserialize, Serialize};
+
+pub mod history;
+
+/// The kind of an [`Event`] without its details.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    BecameMates,
+    ChangedGender,
+    ChangedLifeStage,
+    Died,
+    Separated,
+    WasBorn,
+    WasSpawned,
+}
+
+/// The type of an [`Event`] & the [`Characters`](crate::data::character::Character) involved.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum EventType {
+    /// 2 [`Characters`](crate::data::character::Character) became mates.
+    BecameMates(CharacterId, CharacterId),
+    /// The [`Character`](crate::data::character::Character) changed its gender.
+    ChangedGender(CharacterId, Gender),
+    /// The [`Character`](crate::data::character::Character) reached a new [`LifeStage`](crate::data::character::race::stage::LifeStage).
+    ChangedLifeStage(CharacterId, LifeStageId),
+    /// The [`Character`](crate::data::character::Character) died.
+    Died(CharacterId, DeathCause),
+    /// 2 mates separated.
+    Separated(CharacterId, CharacterId),
+    /// The [`Character`](crate::data::character::Character) was born.
+    WasBorn {
+        character: CharacterId,
+        mother: CharacterId,
+        father: CharacterId,
+    },
+    /// The [`Character`](crate::data::character::Character) was spawned by a single parent.
+    WasSpawned {
+        character: CharacterId,
+        parent: CharacterId,
+    },
+}
+
+impl EventType {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            EventType::BecameMates(_, _) => EventKind::BecameMates,
+            EventType::ChangedGender(_, _) => EventKind::ChangedGender,
+            EventType::ChangedLifeStage(_, _) => EventKind::ChangedLifeStage,
+            EventType::Died(_, _) => EventKind::Died,
+            EventType::Separated(_, _) => EventKind::Separated,
+            EventType::WasBorn { .. } => EventKind::WasBorn,
+            EventType::WasSpawned { .. } => EventKind::WasSpawned,
+        }
+    }
+
+    /// Returns all [`Characters`](crate::data::character::Character) involved in the event.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::CharacterId;
+    ///# use age_of_dragons_core::data::character::death::DeathCause::Disease;
+    ///# use age_of_dragons_core::data::event::EventType;
+    /// let id0 = CharacterId::new(0);
+    /// let id1 = CharacterId::new(1);
+    /// let id2 = CharacterId::new(2);
+    ///
+    /// assert_eq!(EventType::BecameMates(id0, id1).characters(), vec![id0, id1]);
+    /// assert_eq!(EventType::Died(id2, Disease).characters(), vec![id2]);
+    /// assert_eq!(EventType::WasBorn{character: id2, mother: id0, father: id1}.characters(), vec![id2, id0, id1]);
+    /// ```
+    pub fn characters(&self) -> Vec<CharacterId> {
+        match self {
+            EventType::BecameMates(id0, id1) => vec![*id0, *id1],
+            EventType::ChangedGender(id, _) => vec![*id],
+            EventType::ChangedLifeStage(id, _) => vec![*id],
+            EventType::Died(id, _) => vec![*id],
+            EventType::Separated(id0, id1) => vec![*id0, *id1],
+            EventType::WasBorn {
+                character,
+                mother,
+                father,
+            } => vec![*character, *mother, *father],
+            EventType::WasSpawned { character, parent } => vec![*character, *parent],
+        }
+    }
+}
+
+/// Something that happened in the simulation.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    date: Date,
+    event_type: EventType,
+}
+
+impl Event {
+    pub fn new(date: Date, event_type: EventType) -> Self {
+        Self { date, event_type }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn event_type(&self) -> &EventType {
+        &self.event_type
+    }
+
+    /// Is the [`Character`](crate::data::character::Character) involved in the event?
+    pub fn involves(&self, id: CharacterId) -> bool {
+        self.event_type.characters().contains(&id)
+    }
+}
@@ -4,20 +4,30 @@ use crate::data::character::race::manager::RaceMgr;
 use crate::data::character::race::RaceId;
 use crate::data::character::relation::manager::CharacterRelationMgr;
 use crate::data::character::{Character, CharacterId};
+use crate::data::event::history::EventHistory;
+use crate::data::time::calendar::Calendar;
 use crate::data::time::Date;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
 pub mod character;
+pub mod event;
 pub mod name;
 pub mod probability;
+pub mod save;
+pub mod storage;
 pub mod time;
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SimulationData {
     pub race_manager: RaceMgr,
     pub character_manager: CharacterMgr,
     pub relation_manager: CharacterRelationMgr,
+    pub history: EventHistory,
     pub date: Date,
+    pub calendar: Calendar,
+    /// The seed of all the random numbers used to simulate this world.
+    pub seed: u64,
 }
 
 impl SimulationData {
@@ -35,4 +45,12 @@ impl SimulationData {
         self.character_manager
             .create(|id| Character::new(id.id(), name, race, gender, self.date, None))
     }
+
+    /// Validates the references between the managers, which can't be checked while deserializing them.
+    pub fn validate(&self) -> Result<()> {
+        self.race_manager.validate().context("Invalid races")?;
+        self.character_manager
+            .validate(&self.race_manager)
+            .context("Invalid characters")
+    }
 }
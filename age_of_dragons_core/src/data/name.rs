@@ -1,7 +1,9 @@
 use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
 
 /// A string that is a valid name.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct Name(String);
 
 impl Name {
@@ -35,3 +37,17 @@ impl Name {
         &self.0
     }
 }
+
+impl TryFrom<String> for Name {
+    type Error = anyhow::Error;
+
+    fn try_from(name: String) -> Result<Self> {
+        Name::new(name)
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        name.0
+    }
+}
@@ -1,13 +1,27 @@
+use crate::data::time::MONTHS_PER_YEAR;
 use crate::generation::number::RandomNumberGenerator;
 use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// The largest maximum of a [`Probability`], which leaves enough room to scale it by a percentage.
+/// It is also the resolution of monthly probabilities.
+pub const MAX_RESOLUTION: u32 = 10_000_000;
 
 /// The probability of an event happening.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ProbabilityData")]
 pub struct Probability {
     threshold: u32,
     max: u32,
 }
 
+/// The unvalidated fields of a [`Probability`], which are deserialized first.
+#[derive(Deserialize)]
+struct ProbabilityData {
+    threshold: u32,
+    max: u32,
+}
+
 impl Probability {
     /// Creates a probability, if possible:
     ///
@@ -18,10 +32,18 @@ impl Probability {
     /// assert!(Probability::new(2, 2).is_ok());
     /// assert!(Probability::new(3, 4).is_ok());
     /// assert!(Probability::new(3, 5).is_ok());
+    /// assert!(Probability::new(1, 10_000_000).is_ok());
+    /// assert!(Probability::new(1, 10_000_001).is_err());
     /// ```
     pub fn new(threshold: u32, max: u32) -> Result<Self> {
         if threshold == 0 {
             bail!("The threshold must be greater than 0!");
+        } else if max > MAX_RESOLUTION {
+            bail!(
+                "The maximum {} must not be larger than {}!",
+                max,
+                MAX_RESOLUTION
+            );
         } else if max < threshold {
             bail!(
                 "The maximum {} must not be smaller than the threshold {}!",
@@ -33,6 +55,87 @@ impl Probability {
         Ok(Probability { threshold, max })
     }
 
+    /// Returns the probability as a number between 0 & 1:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::probability::Probability;
+    /// assert_eq!(Probability::new(1, 4).unwrap().as_f64(), 0.25);
+    /// assert_eq!(Probability::new(3, 3).unwrap().as_f64(), 1.0);
+    /// ```
+    pub fn as_f64(&self) -> f64 {
+        self.threshold as f64 / self.max as f64
+    }
+
+    /// Scales the probability by a percentage, but never above a certain event.
+    /// Returns [`None`], if the event becomes impossible:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::probability::Probability;
+    /// let probability = Probability::new(1, 4).unwrap();
+    ///
+    /// assert_eq!(probability.scale(0), None);
+    /// assert_eq!(probability.scale(100), Some(probability));
+    /// assert_eq!(probability.scale(50), Probability::new(50, 400).ok());
+    /// assert_eq!(probability.scale(1000), Probability::new(400, 400).ok());
+    /// assert_eq!(Probability::new(1, 10_000_000).unwrap().scale(u32::MAX).unwrap().as_f64(), 1.0);
+    /// ```
+    pub fn scale(&self, percentage: u32) -> Option<Probability> {
+        match percentage {
+            0 => None,
+            100 => Some(*self),
+            _ => {
+                // Can't overflow, because the maximum is limited by MAX_RESOLUTION.
+                let max = self.max * 100;
+                let threshold = (self.threshold as u64 * percentage as u64).min(max as u64) as u32;
+
+                Some(Probability { threshold, max })
+            }
+        }
+    }
+
+    /// Converts a yearly probability into a monthly one, which gives the same probability over the 12 months of a year.
+    /// Certain events stay certain & the result is rounded up to a resolution of [`MAX_RESOLUTION`]:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::probability::Probability;
+    /// let per_year = |p: Probability| 1.0 - (1.0 - p.per_month().as_f64()).powi(12);
+    ///
+    /// assert!((per_year(Probability::new(1, 4).unwrap()) - 0.25).abs() < 1e-5);
+    /// assert!((per_year(Probability::new(1, 1000).unwrap()) - 0.001).abs() < 1e-5);
+    /// assert_eq!(per_year(Probability::new(3, 3).unwrap()), 1.0);
+    /// ```
+    pub fn per_month(&self) -> Probability {
+        let max = MAX_RESOLUTION as u128;
+        // Does a monthly threshold reach the yearly probability? The yearly survival is the product
+        // of the monthly survivals, which is rounded up, so that only certain events become certain.
+        let reaches_yearly = |threshold: u128| {
+            let survival = max - threshold;
+            let yearly =
+                (0..MONTHS_PER_YEAR).fold(max, |yearly, _month| (yearly * survival).div_ceil(max));
+
+            yearly * self.max as u128 <= (self.max - self.threshold) as u128 * max
+        };
+
+        // Binary search for the smallest monthly threshold, that reaches the yearly probability.
+        let mut low = 1;
+        let mut high = max;
+
+        while low < high {
+            let middle = (low + high) / 2;
+
+            if reaches_yearly(middle) {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        Probability {
+            threshold: low as u32,
+            max: MAX_RESOLUTION,
+        }
+    }
+
     /// Check if the event is happening.
     ///
     /// ```
@@ -52,6 +155,14 @@ impl Probability {
     }
 }
 
+impl TryFrom<ProbabilityData> for Probability {
+    type Error = anyhow::Error;
+
+    fn try_from(data: ProbabilityData) -> Result<Self> {
+        Probability::new(data.threshold, data.max)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +178,35 @@ mod tests {
         assert!(!Probability::new(10, 100).unwrap().check(&rng, 0));
         assert!(Probability::new(11, 100).unwrap().check(&rng, 0));
     }
+
+    #[test]
+    fn test_deserialize() {
+        let probability: Probability = ron::from_str("(threshold: 1, max: 4)").unwrap();
+
+        assert_eq!(probability, Probability::new(1, 4).unwrap());
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(ron::from_str::<Probability>("(threshold: 1, max: 0)").is_err());
+        assert!(ron::from_str::<Probability>("(threshold: 0, max: 4)").is_err());
+        assert!(ron::from_str::<Probability>("(threshold: 1, max: 10000001)").is_err());
+    }
+
+    #[test]
+    fn test_per_month_is_monotonic() {
+        let monthly: Vec<u32> = [1, 2, 100, 9_999_999, 10_000_000]
+            .into_iter()
+            .map(|threshold| {
+                Probability::new(threshold, MAX_RESOLUTION)
+                    .unwrap()
+                    .per_month()
+                    .threshold
+            })
+            .collect();
+
+        assert_eq!(monthly[0], 1);
+        assert!(monthly.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(monthly[4], MAX_RESOLUTION);
+    }
 }
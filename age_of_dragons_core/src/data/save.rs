@@ -0,0 +1,173 @@
+use crate::data::SimulationData;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The version of the save format. It needs to be increased for every incompatible change.
+pub const SAVE_VERSION: u32 = 14;
+
+/// The content of a save file.
+#[derive(Serialize)]
+struct SaveFile<'a> {
+    version: u32,
+    data: &'a SimulationData,
+}
+
+/// Only the version of a save file, which is checked before loading the rest.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct LoadFile {
+    data: SimulationData,
+}
+
+/// Converts the [`SimulationData`] into a string.
+pub fn save_to_string(data: &SimulationData) -> Result<String> {
+    let file = SaveFile {
+        version: SAVE_VERSION,
+        data,
+    };
+
+    ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())
+        .context("Failed to serialize the simulation data")
+}
+
+/// Converts a string into [`SimulationData`], if the version matches & the data is valid:
+///
+/// ```
+///# use age_of_dragons_core::data::save::load_from_str;
+/// assert!(load_from_str("(version: 0, data: ())").is_err());
+/// assert!(load_from_str("invalid").is_err());
+/// ```
+pub fn load_from_str(string: &str) -> Result<SimulationData> {
+    let header: SaveHeader = ron::from_str(string).context("Failed to read the save version")?;
+
+    if header.version != SAVE_VERSION {
+        bail!(
+            "The save version {} doesn't match the supported version {}!",
+            header.version,
+            SAVE_VERSION
+        );
+    }
+
+    let file: LoadFile = ron::from_str(string).context("Failed to deserialize the save")?;
+    file.data.validate().context("The save is invalid")?;
+
+    Ok(file.data)
+}
+
+/// Writes the [`SimulationData`] to a file.
+pub fn save(data: &SimulationData, path: &Path) -> Result<()> {
+    let string = save_to_string(data)?;
+    fs::write(path, string).with_context(|| format!("Failed to write save file {:?}", path))
+}
+
+/// Reads the [`SimulationData`] from a file.
+pub fn load(path: &Path) -> Result<SimulationData> {
+    let string =
+        fs::read_to_string(path).with_context(|| format!("Failed to read save file {:?}", path))?;
+    load_from_str(&string).with_context(|| format!("Failed to load save file {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::gender::Gender::{Female, Genderless, Male};
+    use crate::data::character::race::tests::create_mortal_race;
+    use crate::data::character::relation::CharacterRelationType::Mate;
+    use crate::data::character::CharacterId;
+
+    #[test]
+    fn test_save_and_load() {
+        let data = init_data();
+
+        let string = save_to_string(&data).unwrap();
+
+        assert_eq!(load_from_str(&string).unwrap(), data);
+    }
+
+    #[test]
+    fn test_save_and_load_file() {
+        let data = init_data();
+        let file = format!("age_of_dragons_test_save_{}.ron", std::process::id());
+        let path = std::env::temp_dir().join(file);
+
+        save(&data, &path).unwrap();
+
+        assert_eq!(load(&path).unwrap(), data);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_unknown_file() {
+        assert!(load(Path::new("unknown.ron")).is_err());
+    }
+
+    #[test]
+    fn test_load_wrong_version() {
+        let string = save_to_string(&init_data()).unwrap().replacen(
+            &format!("version: {}", SAVE_VERSION),
+            "version: 999",
+            1,
+        );
+
+        assert!(load_from_str(&string).is_err());
+    }
+
+    #[test]
+    fn test_load_invalid_character() {
+        let mut data = init_data();
+        data.character_manager
+            .get_mut(CharacterId::new(0))
+            .unwrap()
+            .set_gender(Genderless);
+        let string = save_to_string(&data).unwrap();
+
+        let error = format!("{:?}", load_from_str(&string).unwrap_err());
+
+        assert!(error.contains("The save is invalid"), "{}", error);
+        assert!(error.contains("Character 0 is invalid"), "{}", error);
+    }
+
+    #[test]
+    fn test_load_archived_living_character() {
+        let mut data = init_data();
+        data.character_manager.archive(CharacterId::new(1));
+        let string = save_to_string(&data).unwrap();
+
+        assert!(load_from_str(&string).is_err());
+    }
+
+    #[test]
+    fn test_load_invalid_race() {
+        let string = save_to_string(&init_data()).unwrap();
+        // the adult life stage ends before the child one
+        let invalid = string.replacen("years: 3,", "years: 0,", 1);
+
+        assert_ne!(string, invalid);
+
+        let error = format!("{:?}", load_from_str(&invalid).unwrap_err());
+
+        assert!(
+            error.contains("Failed to deserialize the save"),
+            "{}",
+            error
+        );
+        assert!(error.contains("ends before previous stages"), "{}", error);
+    }
+
+    fn init_data() -> SimulationData {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+        data.date.increase_year();
+        data
+    }
+}
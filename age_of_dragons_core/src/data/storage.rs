@@ -0,0 +1,279 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// The typed id of an element in a [`Storage`].
+pub trait StorageId: Copy {
+    fn new(id: usize) -> Self;
+
+    fn id(&self) -> usize;
+}
+
+/// Stores elements of the same type, which are accessed with their typed [`id`](StorageId).
+///
+/// Elements can be archived, if they are no longer relevant for the simulation.
+/// They are still accessible by id, but skipped by [`Storage::get_active`].
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "StorageData<T>",
+    bound(deserialize = "T: Deserialize<'de>")
+)]
+pub struct Storage<I, T> {
+    elements: Vec<T>,
+    /// The sorted ids of all elements, that are not archived.
+    active: Vec<usize>,
+    #[serde(skip)]
+    phantom: PhantomData<I>,
+}
+
+/// The unvalidated fields of a [`Storage`], which are deserialized first.
+#[derive(Deserialize)]
+struct StorageData<T> {
+    elements: Vec<T>,
+    active: Vec<usize>,
+}
+
+impl<I: StorageId, T> Storage<I, T> {
+    /// Uses the function *f* to create an element with the next id:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::RaceId;
+    ///# use age_of_dragons_core::data::storage::Storage;
+    /// let mut storage: Storage<RaceId, &str> = Storage::default();
+    ///
+    /// assert_eq!(storage.create(|_id| Ok("A")).unwrap(), RaceId::new(0));
+    /// assert_eq!(storage.create(|_id| Ok("B")).unwrap(), RaceId::new(1));
+    /// assert!(storage.create(|_id| anyhow::bail!("Invalid")).is_err());
+    /// assert_eq!(storage.get_all(), &["A", "B"]);
+    /// ```
+    pub fn create<F>(&mut self, f: F) -> Result<I>
+    where
+        F: FnOnce(I) -> Result<T>,
+    {
+        let id = I::new(self.elements.len());
+        self.elements.push(f(id)?);
+        self.active.push(id.id());
+        Ok(id)
+    }
+
+    /// Returns all elements, including the archived ones.
+    pub fn get_all(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns all elements, that are not archived.
+    pub fn get_active(&self) -> impl Iterator<Item = &T> {
+        self.active.iter().map(|&index| &self.elements[index])
+    }
+
+    /// Returns all elements matching the predicate, including the archived ones.
+    pub fn filter<P>(&self, predicate: P) -> impl Iterator<Item = &T>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.elements
+            .iter()
+            .filter(move |element| predicate(element))
+    }
+
+    pub fn get(&self, id: I) -> Option<&T> {
+        self.elements.get(id.id())
+    }
+
+    pub fn get_mut(&mut self, id: I) -> Option<&mut T> {
+        self.elements.get_mut(id.id())
+    }
+
+    /// The number of elements, including the archived ones.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Archives an element, which can still be accessed by its id:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::RaceId;
+    ///# use age_of_dragons_core::data::storage::Storage;
+    /// let mut storage: Storage<RaceId, &str> = Storage::default();
+    /// let id0 = storage.create(|_id| Ok("A")).unwrap();
+    /// let id1 = storage.create(|_id| Ok("B")).unwrap();
+    ///
+    /// storage.archive(id0);
+    ///
+    /// assert!(storage.is_archived(id0));
+    /// assert!(!storage.is_archived(id1));
+    /// assert_eq!(storage.get(id0), Some(&"A"));
+    /// assert_eq!(storage.get_active().collect::<Vec<_>>(), vec![&"B"]);
+    /// ```
+    ///
+    /// Each call is linear in the number of active elements, so use [`Storage::archive_all`] for many elements.
+    pub fn archive(&mut self, id: I) {
+        self.archive_all([id]);
+    }
+
+    /// Archives many elements at once, with a single pass over the active elements:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::race::RaceId;
+    ///# use age_of_dragons_core::data::storage::Storage;
+    /// let mut storage: Storage<RaceId, &str> = Storage::default();
+    /// let id0 = storage.create(|_id| Ok("A")).unwrap();
+    /// let id1 = storage.create(|_id| Ok("B")).unwrap();
+    /// let id2 = storage.create(|_id| Ok("C")).unwrap();
+    ///
+    /// storage.archive_all([id2, id0]);
+    ///
+    /// assert_eq!(storage.get_active().collect::<Vec<_>>(), vec![&"B"]);
+    /// ```
+    pub fn archive_all<C: IntoIterator<Item = I>>(&mut self, ids: C) {
+        let mut archived: Vec<usize> = ids.into_iter().map(|id| id.id()).collect();
+
+        if archived.is_empty() {
+            return;
+        }
+
+        archived.sort_unstable();
+        self.active
+            .retain(|index| archived.binary_search(index).is_err());
+    }
+
+    /// Is the element archived? Unknown ids are never archived.
+    pub fn is_archived(&self, id: I) -> bool {
+        id.id() < self.elements.len() && self.active.binary_search(&id.id()).is_err()
+    }
+}
+
+impl<I: StorageId, T: Default> Storage<I, T> {
+    /// Returns a mutable element, after adding default elements up to its id, if needed.
+    pub fn get_mut_or_default(&mut self, id: I) -> &mut T {
+        while self.elements.len() <= id.id() {
+            self.active.push(self.elements.len());
+            self.elements.push(T::default());
+        }
+
+        &mut self.elements[id.id()]
+    }
+}
+
+impl<I, T> TryFrom<StorageData<T>> for Storage<I, T> {
+    type Error = anyhow::Error;
+
+    fn try_from(data: StorageData<T>) -> Result<Self> {
+        if let Some(window) = data.active.windows(2).find(|window| window[0] >= window[1]) {
+            bail!(
+                "The active id {} is not after {}, so they aren't sorted!",
+                window[1],
+                window[0]
+            );
+        } else if let Some(id) = data.active.last().filter(|&&id| id >= data.elements.len()) {
+            bail!(
+                "The active id {} is unknown, because there are only {} elements!",
+                id,
+                data.elements.len()
+            );
+        }
+
+        Ok(Self {
+            elements: data.elements,
+            active: data.active,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<I, T> Default for Storage<I, T> {
+    fn default() -> Self {
+        Self {
+            elements: Vec::new(),
+            active: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::CharacterId;
+
+    #[test]
+    fn test_get_unknown_id() {
+        let mut storage: Storage<CharacterId, u32> = Storage::default();
+        let id = CharacterId::new(0);
+
+        assert_eq!(storage.get(id), None);
+        assert_eq!(storage.get_mut(id), None);
+        assert!(!storage.is_archived(id));
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn test_get_mut_or_default() {
+        let mut storage: Storage<CharacterId, u32> = Storage::default();
+
+        *storage.get_mut_or_default(CharacterId::new(2)) = 5;
+
+        assert_eq!(storage.get_all(), &[0, 0, 5]);
+        assert_eq!(storage.get_active().count(), 3);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut storage: Storage<CharacterId, u32> = Storage::default();
+
+        for i in 0..6 {
+            let id = storage.create(|_id| Ok(i)).unwrap();
+
+            if i < 2 {
+                storage.archive(id);
+            }
+        }
+
+        assert_eq!(
+            storage.filter(|i| i % 2 == 0).collect::<Vec<_>>(),
+            vec![&0, &2, &4]
+        );
+        assert_eq!(storage.get_active().count(), 4);
+        assert_eq!(storage.len(), 6);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let storage: Storage<CharacterId, u32> =
+            ron::from_str("(elements: [4, 5, 6], active: [0, 2])").unwrap();
+
+        assert_eq!(storage.get_active().collect::<Vec<_>>(), vec![&4, &6]);
+        assert!(storage.is_archived(CharacterId::new(1)));
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let load = |string| ron::from_str::<Storage<CharacterId, u32>>(string);
+
+        assert!(load("(elements: [4, 5, 6], active: [2, 0])").is_err());
+        assert!(load("(elements: [4, 5, 6], active: [1, 1])").is_err());
+        assert!(load("(elements: [4, 5, 6], active: [0, 3])").is_err());
+    }
+
+    #[test]
+    fn test_archive_all_keeps_order() {
+        let mut storage: Storage<CharacterId, u32> = Storage::default();
+
+        for i in 0..6 {
+            storage.create(|_id| Ok(i)).unwrap();
+        }
+
+        storage.archive_all([4, 1, 4].map(CharacterId::new));
+        storage.archive_all([]);
+
+        assert_eq!(
+            storage.get_active().collect::<Vec<_>>(),
+            vec![&0, &2, &3, &5]
+        );
+        assert!(storage.is_archived(CharacterId::new(4)));
+    }
+}
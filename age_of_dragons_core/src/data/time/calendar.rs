@@ -0,0 +1,277 @@
+use crate::data::name::Name;
+use crate::data::time::era::Era;
+use crate::data::time::{Date, MONTHS_PER_YEAR};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// A month of a [`Calendar`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Month {
+    name: Name,
+    days: u32,
+}
+
+impl Month {
+    /// Creates a month, if valid.
+    pub fn new<S: Into<String>>(name: S, days: u32) -> Result<Self> {
+        let name = Name::new(name).context("Failed to create month")?;
+
+        if days == 0 {
+            bail!("Month {} has no days!", name.to_str());
+        }
+
+        Ok(Self { name, days })
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+}
+
+/// Defines the names & lengths of the months of a year and the [`eras`](Era) used to display dates.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CalendarDefinition", into = "CalendarDefinition")]
+pub struct Calendar {
+    months: Vec<Month>,
+    /// Sorted by their start year.
+    eras: Vec<Era>,
+}
+
+/// The serialized form of a [`Calendar`], which is validated when loading it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CalendarDefinition {
+    months: Vec<Month>,
+    #[serde(default)]
+    eras: Vec<Era>,
+}
+
+impl Calendar {
+    /// Creates a calendar, if it has the right number of months:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::calendar::{Calendar, Month};
+    /// let months: Vec<Month> = (0..12)
+    ///     .map(|i| Month::new(format!("M{}", i), 30).unwrap())
+    ///     .collect();
+    ///
+    /// assert!(Calendar::new(months[..11].to_vec(), vec![]).is_err());
+    /// assert_eq!(Calendar::new(months, vec![]).unwrap().days_per_year(), 360);
+    /// ```
+    ///
+    /// The [`eras`](Era) must be sorted by their start year.
+    pub fn new(months: Vec<Month>, eras: Vec<Era>) -> Result<Self> {
+        if months.len() != MONTHS_PER_YEAR as usize {
+            bail!(
+                "The calendar has {} months instead of {}!",
+                months.len(),
+                MONTHS_PER_YEAR
+            );
+        }
+
+        for (i, pair) in eras.windows(2).enumerate() {
+            if pair[1].start_year() <= pair[0].start_year() {
+                bail!(
+                    "The calendar's era {} starts before the previous one!",
+                    i + 1
+                );
+            }
+        }
+
+        Ok(Self { months, eras })
+    }
+
+    /// Loads a calendar from a file in the RON format.
+    pub fn load(path: &Path) -> Result<Self> {
+        let string = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read calendar file {:?}", path))?;
+        Self::load_from_str(&string)
+            .with_context(|| format!("Failed to load calendar file {:?}", path))
+    }
+
+    /// Loads a calendar from a string in the RON format.
+    pub fn load_from_str(string: &str) -> Result<Self> {
+        ron::from_str(string).context("Failed to parse calendar")
+    }
+
+    pub fn months(&self) -> &[Month] {
+        &self.months
+    }
+
+    pub fn eras(&self) -> &[Era] {
+        &self.eras
+    }
+
+    pub fn get_month(&self, date: Date) -> &Month {
+        &self.months[date.month() as usize]
+    }
+
+    /// Returns the latest [`Era`] that started before or in the year of the [`Date`].
+    pub fn get_era(&self, date: Date) -> Option<&Era> {
+        self.eras
+            .iter()
+            .rev()
+            .find(|era| era.start_year() <= date.year())
+    }
+
+    pub fn days_per_year(&self) -> u32 {
+        self.months.iter().map(|month| month.days).sum()
+    }
+
+    /// Formats a [`Date`] with the name of its month:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::calendar::Calendar;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let calendar = Calendar::default();
+    ///
+    /// assert_eq!(calendar.format(Date::with_month(312, 2).unwrap()), "March 312");
+    /// ```
+    ///
+    /// and its [`Era`], if one already started:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::calendar::Calendar;
+    ///# use age_of_dragons_core::data::time::era::Era;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let months = Calendar::default().months().to_vec();
+    /// let eras = vec![Era::new("Third Age", 100, 1).unwrap()];
+    /// let calendar = Calendar::new(months, eras).unwrap();
+    ///
+    /// assert_eq!(calendar.format(Date::new(99)), "January 99");
+    /// assert_eq!(
+    ///     calendar.format(Date::with_month(411, 2).unwrap()),
+    ///     "March, Year 312 of the Third Age"
+    /// );
+    /// ```
+    pub fn format(&self, date: Date) -> String {
+        let month = self.get_month(date).name.to_str();
+
+        if let Some((era, year)) = self
+            .get_era(date)
+            .and_then(|era| era.get_year(date).map(|year| (era, year)))
+        {
+            format!("{}, Year {} of the {}", month, year, era.name().to_str())
+        } else {
+            format!("{} {}", month, date.year())
+        }
+    }
+}
+
+impl TryFrom<CalendarDefinition> for Calendar {
+    type Error = anyhow::Error;
+
+    fn try_from(definition: CalendarDefinition) -> Result<Self> {
+        Calendar::new(definition.months, definition.eras)
+    }
+}
+
+impl From<Calendar> for CalendarDefinition {
+    fn from(calendar: Calendar) -> Self {
+        CalendarDefinition {
+            months: calendar.months,
+            eras: calendar.eras,
+        }
+    }
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        let months = [
+            ("January", 31),
+            ("February", 28),
+            ("March", 31),
+            ("April", 30),
+            ("May", 31),
+            ("June", 30),
+            ("July", 31),
+            ("August", 31),
+            ("September", 30),
+            ("October", 31),
+            ("November", 30),
+            ("December", 31),
+        ]
+        .into_iter()
+        .map(|(name, days)| Month::new(name, days).unwrap())
+        .collect();
+
+        Self::new(months, vec![]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_month() {
+        assert!(Month::new("", 30).is_err());
+        assert!(Month::new("Month", 0).is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        let calendar = Calendar::default();
+
+        assert_eq!(calendar.days_per_year(), 365);
+        assert_eq!(calendar.get_month(Date::new(5)).name().to_str(), "January");
+    }
+
+    #[test]
+    fn test_get_era() {
+        let calendar = init_calendar();
+
+        assert_eq!(calendar.get_era(Date::new(9)), None);
+        assert_eq!(calendar.get_era(Date::new(10)), Some(&calendar.eras()[0]));
+        assert_eq!(calendar.get_era(Date::new(49)), Some(&calendar.eras()[0]));
+        assert_eq!(calendar.get_era(Date::new(50)), Some(&calendar.eras()[1]));
+    }
+
+    #[test]
+    fn test_unsorted_eras() {
+        let months = Calendar::default().months;
+        let eras = vec![
+            Era::new("Second Age", 50, 0).unwrap(),
+            Era::new("First Age", 10, 0).unwrap(),
+        ];
+
+        assert!(Calendar::new(months, eras).is_err());
+    }
+
+    #[test]
+    fn test_load_calendar() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/calendar.ron");
+
+        let calendar = Calendar::load(&path).unwrap();
+
+        assert_eq!(calendar.months().len(), 12);
+        assert!(!calendar.eras().is_empty());
+    }
+
+    #[test]
+    fn test_load_invalid_calendar() {
+        assert!(Calendar::load_from_str("(months: [(name: \"M\", days: 30)])").is_err());
+        assert!(Calendar::load(Path::new("unknown.ron")).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let calendar = init_calendar();
+        let string = ron::to_string(&calendar).unwrap();
+
+        assert_eq!(Calendar::load_from_str(&string).unwrap(), calendar);
+    }
+
+    fn init_calendar() -> Calendar {
+        let eras = vec![
+            Era::new("First Age", 10, 1).unwrap(),
+            Era::new("Second Age", 50, 1).unwrap(),
+        ];
+        Calendar::new(Calendar::default().months, eras).unwrap()
+    }
+}
@@ -0,0 +1,57 @@
+use crate::data::name::Name;
+use crate::data::time::Date;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// An era counts the years since its start for displaying dates, like "Year 312 of the Third Age".
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Era {
+    name: Name,
+    /// The year of the simulation, when the era starts.
+    start_year: u32,
+    /// The number of the era's first year.
+    #[serde(default)]
+    epoch_offset: u32,
+}
+
+impl Era {
+    /// Creates an era, if valid.
+    pub fn new<S: Into<String>>(name: S, start_year: u32, epoch_offset: u32) -> Result<Self> {
+        let name = Name::new(name).context("Failed to create era")?;
+
+        Ok(Self {
+            name,
+            start_year,
+            epoch_offset,
+        })
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn start_year(&self) -> u32 {
+        self.start_year
+    }
+
+    pub fn epoch_offset(&self) -> u32 {
+        self.epoch_offset
+    }
+
+    /// Calculates the year of a [`Date`] in this era, if it isn't before the era's start:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::era::Era;
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let era = Era::new("Third Age", 100, 1).unwrap();
+    ///
+    /// assert_eq!(era.get_year(Date::new(99)), None);
+    /// assert_eq!(era.get_year(Date::new(100)), Some(1));
+    /// assert_eq!(era.get_year(Date::new(411)), Some(312));
+    /// ```
+    pub fn get_year(&self, date: Date) -> Option<u32> {
+        date.year()
+            .checked_sub(self.start_year)
+            .map(|year| year + self.epoch_offset)
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Sub};
+
+pub mod calendar;
+pub mod era;
+
+/// Every year has the same number of months, independent of the [`Calendar`](calendar::Calendar).
+pub const MONTHS_PER_YEAR: u32 = 12;
+
+/// A date with the resolution of months. Internally it counts the months since the start of the simulation.
+#[derive(
+    Default, Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize,
+)]
+pub struct Date(u32);
+
+impl Date {
+    /// Creates the date of the first month of a year.
+    pub fn new(year: u32) -> Self {
+        Date(year * MONTHS_PER_YEAR)
+    }
+
+    /// Creates the date of a specific month of a year, if valid:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::Date;
+    /// let date = Date::with_month(3, 11).unwrap();
+    ///
+    /// assert_eq!(date.year(), 3);
+    /// assert_eq!(date.month(), 11);
+    /// assert!(Date::with_month(3, 12).is_err());
+    /// ```
+    pub fn with_month(year: u32, month: u32) -> Result<Self> {
+        if month >= MONTHS_PER_YEAR {
+            bail!(
+                "The month {} is invalid, because a year only has {} months!",
+                month,
+                MONTHS_PER_YEAR
+            );
+        }
+
+        Ok(Date(year * MONTHS_PER_YEAR + month))
+    }
+
+    pub fn year(&self) -> u32 {
+        self.0 / MONTHS_PER_YEAR
+    }
+
+    /// The month of the year starting with 0.
+    pub fn month(&self) -> u32 {
+        self.0 % MONTHS_PER_YEAR
+    }
+
+    /// The number of months since the start of the simulation.
+    pub fn total_months(&self) -> u32 {
+        self.0
+    }
+
+    /// Calculates the [`Duration`] between 2 dates.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::{Date, Duration};
+    /// let date0 = Date::new(20);
+    /// let date1 = Date::new(5);
+    /// let duration = Duration::new(15);
+    ///
+    /// assert_eq!(date0.get_duration_since(date1), duration);
+    /// assert_eq!(date1.get_duration_since(date0), duration);
+    /// ```
+    pub fn get_duration_since(&self, date: Date) -> Duration {
+        Duration(self.0.abs_diff(date.0))
+    }
+
+    pub fn increase_year(&mut self) {
+        self.0 += MONTHS_PER_YEAR;
+    }
+
+    pub fn increase_month(&mut self) {
+        self.0 += 1;
+    }
+}
+
+/// Moves a date into the future:
+///
+/// ```
+///# use age_of_dragons_core::data::time::{Date, Duration};
+/// let date = Date::with_month(10, 6).unwrap();
+///
+/// assert_eq!(date + Duration::from_months(7), Date::with_month(11, 1).unwrap());
+/// ```
+impl Add<Duration> for Date {
+    type Output = Date;
+
+    fn add(self, duration: Duration) -> Date {
+        Date(self.0 + duration.0)
+    }
+}
+
+/// Moves a date into the past:
+///
+/// ```
+///# use age_of_dragons_core::data::time::{Date, Duration};
+/// let date = Date::with_month(10, 6).unwrap();
+///
+/// assert_eq!(date - Duration::from_months(7), Date::with_month(9, 11).unwrap());
+/// ```
+impl Sub<Duration> for Date {
+    type Output = Date;
+
+    fn sub(self, duration: Duration) -> Date {
+        Date(self.0 - duration.0)
+    }
+}
+
+/// A duration with the resolution of months. It is stored as years & months, which are both optional.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "DurationData", into = "DurationData")]
+pub struct Duration(u32);
+
+/// The unvalidated fields of a [`Duration`], which are deserialized first.
+#[derive(Serialize, Deserialize)]
+struct DurationData {
+    #[serde(default)]
+    years: u32,
+    #[serde(default)]
+    months: u32,
+}
+
+impl Duration {
+    /// Creates a duration of full years.
+    pub fn new(years: u32) -> Self {
+        Duration(years * MONTHS_PER_YEAR)
+    }
+
+    pub fn from_months(months: u32) -> Self {
+        Duration(months)
+    }
+
+    /// The number of full years:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::time::Duration;
+    /// assert_eq!(Duration::from_months(11).year(), 0);
+    /// assert_eq!(Duration::from_months(12).year(), 1);
+    /// assert_eq!(Duration::from_months(35).year(), 2);
+    /// ```
+    pub fn year(&self) -> u32 {
+        self.0 / MONTHS_PER_YEAR
+    }
+
+    pub fn total_months(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        Duration(self.0 + other.0)
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, other: Duration) -> Duration {
+        Duration(self.0 - other.0)
+    }
+}
+
+impl TryFrom<DurationData> for Duration {
+    type Error = anyhow::Error;
+
+    fn try_from(data: DurationData) -> Result<Self> {
+        match data
+            .years
+            .checked_mul(MONTHS_PER_YEAR)
+            .and_then(|months| months.checked_add(data.months))
+        {
+            Some(months) => Ok(Duration(months)),
+            None => bail!(
+                "The duration of {} years & {} months is too long!",
+                data.years,
+                data.months
+            ),
+        }
+    }
+}
+
+impl From<Duration> for DurationData {
+    fn from(duration: Duration) -> Self {
+        Self {
+            years: duration.year(),
+            months: duration.0 % MONTHS_PER_YEAR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increase() {
+        let mut date = Date::with_month(2, 10).unwrap();
+
+        date.increase_month();
+        assert_eq!(date, Date::with_month(2, 11).unwrap());
+
+        date.increase_month();
+        assert_eq!(date, Date::new(3));
+
+        date.increase_year();
+        assert_eq!(date, Date::new(4));
+    }
+
+    #[test]
+    fn test_duration_arithmetic() {
+        let duration = Duration::new(1) + Duration::from_months(5);
+
+        assert_eq!(duration.total_months(), 17);
+        assert_eq!(duration - Duration::new(1), Duration::from_months(5));
+    }
+
+    #[test]
+    fn test_deserialize_duration() {
+        let load = |string| ron::from_str::<Duration>(string).unwrap();
+
+        assert_eq!(load("(years: 1, months: 3)"), Duration::from_months(15));
+        assert_eq!(load("(months: 18)"), Duration::from_months(18));
+        assert_eq!(load("(years: 2)"), Duration::new(2));
+        assert_eq!(load("()"), Duration::default());
+    }
+
+    #[test]
+    fn test_deserialize_invalid_duration() {
+        assert!(ron::from_str::<Duration>("(years: 4294967295)").is_err());
+        assert!(ron::from_str::<Duration>("(years: 1, months: 4294967295)").is_err());
+    }
+
+    #[test]
+    fn test_serialize_duration() {
+        let string = ron::to_string(&Duration::from_months(15)).unwrap();
+
+        assert_eq!(string, "(years:1,months:3)");
+        assert_eq!(
+            ron::from_str::<Duration>(&string).unwrap(),
+            Duration::from_months(15)
+        );
+    }
+}
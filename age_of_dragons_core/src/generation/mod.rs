@@ -1 +1,3 @@
+pub mod name;
 pub mod number;
+pub mod world;
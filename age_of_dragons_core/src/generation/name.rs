@@ -0,0 +1,266 @@
+use crate::data::character::race::Race;
+use crate::data::character::Character;
+use crate::data::name::Name;
+use crate::generation::number::RandomNumberGenerator;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Generates names by combining syllables from tables:
+/// a beginning, some middles & an ending.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "NameGeneratorData")]
+pub struct NameGenerator {
+    beginnings: Vec<String>,
+    middles: Vec<String>,
+    endings: Vec<String>,
+    /// The maximum number of syllables between the beginning & the ending.
+    max_middles: u32,
+}
+
+/// The unvalidated fields of a [`NameGenerator`], which are deserialized first.
+#[derive(Deserialize)]
+struct NameGeneratorData {
+    beginnings: Vec<String>,
+    #[serde(default)]
+    middles: Vec<String>,
+    #[serde(default)]
+    endings: Vec<String>,
+    #[serde(default)]
+    max_middles: u32,
+}
+
+impl NameGenerator {
+    /// Creates a name generator, if valid:
+    ///
+    /// ```
+    ///# use age_of_dragons_core::generation::name::NameGenerator;
+    /// let syllables = vec!["a".to_string()];
+    ///
+    /// assert!(NameGenerator::new(syllables.clone(), vec![], syllables.clone(), 0).is_ok());
+    /// assert!(NameGenerator::new(vec![], vec![], syllables.clone(), 0).is_err());
+    /// assert!(NameGenerator::new(syllables.clone(), vec![], syllables.clone(), 1).is_err());
+    /// assert!(NameGenerator::new(syllables, vec![], vec![" ".to_string()], 0).is_err());
+    /// ```
+    pub fn new(
+        beginnings: Vec<String>,
+        middles: Vec<String>,
+        endings: Vec<String>,
+        max_middles: u32,
+    ) -> Result<Self> {
+        if beginnings.is_empty() {
+            bail!("The name generator has no beginnings!");
+        } else if middles.is_empty() && max_middles > 0 {
+            bail!(
+                "The name generator has no middles, but allows {}!",
+                max_middles
+            );
+        } else if let Some(syllable) = beginnings
+            .iter()
+            .chain(middles.iter())
+            .chain(endings.iter())
+            .find(|syllable| syllable.trim().is_empty())
+        {
+            bail!(
+                "The name generator has the invalid syllable '{}'!",
+                syllable
+            );
+        }
+
+        Ok(Self {
+            beginnings,
+            middles,
+            endings,
+            max_middles,
+        })
+    }
+
+    /// Generates a name. Each random number is used only once, so the index is increased after each usage.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::generation::name::NameGenerator;
+    ///# use age_of_dragons_core::generation::number::RandomNumberGenerator;
+    /// let generator = NameGenerator::new(
+    ///     vec!["Ar".to_string(), "Bel".to_string()],
+    ///     vec!["a".to_string(), "o".to_string()],
+    ///     vec!["th".to_string(), "x".to_string()],
+    ///     2,
+    /// ).unwrap();
+    /// let rng = RandomNumberGenerator::Mock { values: vec![1, 2, 0, 1, 1], default: 0 };
+    /// let mut index = 0;
+    ///
+    /// assert_eq!(generator.generate(&rng, &mut index).to_str(), "Belaox");
+    /// assert_eq!(index, 5);
+    /// ```
+    pub fn generate(&self, rng: &RandomNumberGenerator, index: &mut usize) -> Name {
+        let mut name = select(&self.beginnings, rng, index).to_string();
+
+        if !self.middles.is_empty() {
+            let count = rng.generate(next_index(index), self.max_middles + 1);
+
+            for _i in 0..count {
+                name.push_str(select(&self.middles, rng, index));
+            }
+        }
+
+        if !self.endings.is_empty() {
+            name.push_str(select(&self.endings, rng, index));
+        }
+
+        Name::new(name).expect("Generated name is invalid!")
+    }
+}
+
+impl TryFrom<NameGeneratorData> for NameGenerator {
+    type Error = anyhow::Error;
+
+    fn try_from(data: NameGeneratorData) -> Result<Self> {
+        NameGenerator::new(
+            data.beginnings,
+            data.middles,
+            data.endings,
+            data.max_middles,
+        )
+    }
+}
+
+/// Generates the surname of a [`Character`] based on the [`NamingRule`](crate::data::character::race::naming::NamingRule) of its [`Race`].
+/// Founders of a clan get a new name from the race's [`NameGenerator`].
+pub fn generate_surname(
+    race: &Race,
+    father: Option<&Character>,
+    mother: Option<&Character>,
+    rng: &RandomNumberGenerator,
+    index: &mut usize,
+) -> Result<Option<Name>> {
+    let rule = race.naming_rule();
+
+    Ok(rule.derive_surname(father, mother)?.or_else(|| {
+        if rule.founds_clan() {
+            race.name_generator()
+                .map(|generator| generator.generate(rng, index))
+        } else {
+            None
+        }
+    }))
+}
+
+/// Randomly selects a syllable from a non-empty table.
+fn select<'a>(syllables: &'a [String], rng: &RandomNumberGenerator, index: &mut usize) -> &'a str {
+    let i = rng.generate(next_index(index), syllables.len() as u32);
+    &syllables[i as usize]
+}
+
+/// Returns the current index & increases it.
+fn next_index(index: &mut usize) -> usize {
+    let current = *index;
+    *index += 1;
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::race::naming::NamingRule;
+    use crate::data::character::race::tests::TestRace;
+
+    #[test]
+    fn test_same_index_gives_same_name() {
+        let generator = init_generator();
+        let rng = RandomNumberGenerator::new_hash(42, 0, 0);
+
+        assert_eq!(
+            generator.generate(&rng, &mut 10),
+            generator.generate(&rng, &mut 10)
+        );
+    }
+
+    #[test]
+    fn test_only_beginnings() {
+        let generator = NameGenerator::new(vec!["Ka".to_string()], vec![], vec![], 0).unwrap();
+        let rng = RandomNumberGenerator::Mock {
+            values: vec![],
+            default: 0,
+        };
+        let mut index = 0;
+
+        assert_eq!(generator.generate(&rng, &mut index).to_str(), "Ka");
+        assert_eq!(index, 1);
+    }
+
+    #[test]
+    fn test_names_vary() {
+        let generator = init_generator();
+        let rng = RandomNumberGenerator::new_hash(42, 0, 0);
+        let mut index = 0;
+
+        let names: Vec<Name> = (0..10)
+            .map(|_i| generator.generate(&rng, &mut index))
+            .collect();
+
+        assert!(names.iter().any(|name| name != &names[0]));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let generator = init_generator();
+        let string = ron::to_string(&generator).unwrap();
+
+        assert_eq!(ron::from_str::<NameGenerator>(&string).unwrap(), generator);
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let string = "(beginnings: [], middles: [], endings: [\"a\"], max_middles: 0)";
+
+        assert!(ron::from_str::<NameGenerator>(string).is_err());
+    }
+
+    #[test]
+    fn test_clan_founder_gets_surname() {
+        let race = init_race(NamingRule::Clan);
+        let rng = RandomNumberGenerator::Mock {
+            values: vec![],
+            default: 0,
+        };
+        let mut index = 0;
+
+        let surname = generate_surname(&race, None, None, &rng, &mut index).unwrap();
+
+        assert_eq!(surname, Some(Name::new("Arth").unwrap()));
+        assert!(index > 0);
+    }
+
+    #[test]
+    fn test_without_parents_no_patronym() {
+        let race = init_race(NamingRule::Patronymic);
+        let rng = RandomNumberGenerator::new_hash(42, 0, 0);
+        let mut index = 0;
+
+        assert_eq!(
+            generate_surname(&race, None, None, &rng, &mut index).unwrap(),
+            None
+        );
+        assert_eq!(index, 0);
+    }
+
+    fn init_race(naming_rule: NamingRule) -> Race {
+        TestRace {
+            name_generator: Some(init_generator()),
+            naming_rule,
+            ..TestRace::default()
+        }
+        .build(0)
+        .unwrap()
+    }
+
+    fn init_generator() -> NameGenerator {
+        let to_strings = |syllables: &[&str]| syllables.iter().map(|s| s.to_string()).collect();
+        NameGenerator::new(
+            to_strings(&["Ar", "Bel", "Cor", "Dra"]),
+            to_strings(&["a", "e", "o", "ri"]),
+            to_strings(&["th", "x", "gon", "mir"]),
+            2,
+        )
+        .unwrap()
+    }
+}
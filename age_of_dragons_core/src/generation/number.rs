@@ -1,16 +1,17 @@
-use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
-use std::hash::Hasher;
 
+/// Generates random numbers that only depend on the inputs.
+///
+/// The same seed, simulation step, usage & index always give the same number on every platform & compiler version.
 #[derive(Clone, Debug, PartialEq)]
 pub enum RandomNumberGenerator {
-    Hash { year: u32, usage: u32 },
+    Hash { seed: u64, step: u32, usage: u32 },
     Mock { values: Vec<u64>, default: u64 },
 }
 
 impl RandomNumberGenerator {
-    pub fn new_hash(year: u32, usage: u32) -> Self {
-        Self::Hash { year, usage }
+    pub fn new_hash(seed: u64, step: u32, usage: u32) -> Self {
+        Self::Hash { seed, step, usage }
     }
 
     /// Generates a number between 0 and exclusive the maximum.
@@ -20,14 +21,86 @@ impl RandomNumberGenerator {
 
     fn next(&self, index: usize) -> u64 {
         match self {
-            Self::Hash { year, usage } => {
-                let mut hasher = DefaultHasher::new();
-                hasher.write_u32(*year);
-                hasher.write_usize(index);
-                hasher.write_u32(*usage);
-                hasher.finish()
+            Self::Hash { seed, step, usage } => {
+                // The seed is mixed first, so that different seeds don't collide at shifted steps.
+                let hash = mix(mix(*seed) ^ *step as u64);
+                let hash = mix(hash ^ *usage as u64);
+                mix(hash ^ index as u64)
             }
             Self::Mock { values, default } => *values.get(index).unwrap_or(default),
         }
     }
 }
+
+/// The finalizer of SplitMix64, which is a fast & well distributed hash function.
+///
+/// It must never be changed, because it would change the history of every seed.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_is_stable() {
+        let rng = RandomNumberGenerator::new_hash(42, 100, 3);
+
+        let values: Vec<u64> = (0..4).map(|i| rng.next(i)).collect();
+
+        assert_eq!(
+            values,
+            vec![
+                2222967463806970845,
+                13304977372102512701,
+                2367240869317625368,
+                6354020786742146630
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inputs_change_hash() {
+        let rng = RandomNumberGenerator::new_hash(42, 100, 3);
+
+        assert_ne!(rng.next(0), rng.next(1));
+        assert_ne!(
+            rng.next(0),
+            RandomNumberGenerator::new_hash(43, 100, 3).next(0)
+        );
+        assert_ne!(
+            rng.next(0),
+            RandomNumberGenerator::new_hash(42, 101, 3).next(0)
+        );
+        assert_ne!(
+            rng.next(0),
+            RandomNumberGenerator::new_hash(42, 100, 4).next(0)
+        );
+    }
+
+    #[test]
+    fn test_seeds_dont_collide_at_shifted_steps() {
+        for k in 1..16 {
+            let rng = RandomNumberGenerator::new_hash(42, 100, 3);
+            let shifted = RandomNumberGenerator::new_hash(42 ^ k, 100 ^ k as u32, 3);
+
+            assert_ne!(rng.next(0), shifted.next(0));
+        }
+    }
+
+    #[test]
+    fn test_generate_with_mock() {
+        let rng = RandomNumberGenerator::Mock {
+            values: vec![7, 12],
+            default: 3,
+        };
+
+        assert_eq!(rng.generate(0, 5), 2);
+        assert_eq!(rng.generate(1, 5), 2);
+        assert_eq!(rng.generate(2, 5), 3);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::data::character::gender::Gender;
+use crate::data::character::gender::Gender::{Female, Male};
+use crate::data::character::manager::CharacterMgr;
+use crate::data::character::race::manager::RaceMgr;
+use crate::data::character::race::Race;
+use crate::data::character::relation::manager::CharacterRelationMgr;
+use crate::data::character::{Character, CharacterId};
+use crate::data::event::history::EventHistory;
+use crate::data::time::calendar::Calendar;
+use crate::data::time::Date;
+use crate::data::SimulationData;
+use crate::generation::name::generate_surname;
+use crate::generation::number::RandomNumberGenerator;
+use crate::simulation::RandomUsage;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Generates a new world with the [`races`](Race) of a directory, a [`Calendar`] & a pair of dragons.
+pub fn init_simulation(race_dir: &Path, calendar_path: &Path, seed: u64) -> Result<SimulationData> {
+    let race_manager = init_races(race_dir)?;
+    let calendar = Calendar::load(calendar_path)?;
+    let dragon = race_manager
+        .get_all()
+        .iter()
+        .find(|race| race.name().to_str() == "Dragon")
+        .context("The dragon race is missing!")?;
+    let character_manager = init_characters(dragon, seed)?;
+    let relation_manager = CharacterRelationMgr::default();
+
+    Ok(SimulationData {
+        race_manager,
+        character_manager,
+        relation_manager,
+        history: EventHistory::default(),
+        date: Date::new(2),
+        calendar,
+        seed,
+    })
+}
+
+fn init_races(race_dir: &Path) -> Result<RaceMgr> {
+    let mut manager = RaceMgr::default();
+
+    manager.load_dir(race_dir)?;
+
+    Ok(manager)
+}
+
+/// Creates a pair of characters born in year 0 and names them with the race's [`NameGenerator`](crate::generation::name::NameGenerator), if available.
+/// They have no parents, so only founders of clans get surnames.
+fn init_characters(race: &Race, seed: u64) -> Result<CharacterMgr> {
+    let mut manager = CharacterMgr::default();
+    let rng = RandomNumberGenerator::new_hash(seed, 0, RandomUsage::Name as u32);
+    let mut index = 0;
+
+    for gender in [Male, Female] {
+        manager.create(|id| {
+            let name = race
+                .name_generator()
+                .map(|generator| generator.generate(&rng, &mut index).into())
+                .unwrap_or_else(|| format!("D{}", id.id()));
+            let mut character = init_character(id, race, name, gender)?;
+            character.set_surname(generate_surname(race, None, None, &rng, &mut index)?);
+            Ok(character)
+        })?;
+    }
+
+    Ok(manager)
+}
+
+fn init_character(id: CharacterId, race: &Race, name: String, gender: Gender) -> Result<Character> {
+    Character::new(id.id(), name, race, gender, Date::new(0), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_init_simulation() {
+        let data = init_simulation(&get_race_dir(), &get_calendar_path(), 42).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 2);
+        assert_eq!(data.calendar.months()[0].name().to_str(), "Frostwake");
+        assert_eq!(data.date, Date::new(2));
+        assert_eq!(data.seed, 42);
+    }
+
+    #[test]
+    fn test_init_simulation_without_races() {
+        assert!(init_simulation(Path::new("unknown"), &get_calendar_path(), 0).is_err());
+    }
+
+    #[test]
+    fn test_init_simulation_without_calendar() {
+        assert!(init_simulation(&get_race_dir(), Path::new("unknown.ron"), 0).is_err());
+    }
+
+    fn get_race_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/races")
+    }
+
+    fn get_calendar_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/calendar.ron")
+    }
+}
@@ -1,10 +1,30 @@
+use crate::data::character::death::DeathCause;
+use crate::data::character::gender::Gender;
+use crate::data::character::race::bonding::PairBonding;
 use crate::data::character::race::stage::LifeStageId;
+use crate::data::character::relation::CharacterRelationType::Mate;
+use crate::data::character::relation::RelationEnd;
+use crate::data::character::relation::RelationEndReason::Death;
 use crate::data::character::{Character, CharacterId};
+use crate::data::event::EventType;
 use crate::data::SimulationData;
-
-/// Simulates the aging of [`Characters`](Character). They can grow up and die of old age.
-pub fn simulate_aging(data: &mut SimulationData) {
-    for effect in calculate_aging_effects(data) {
+use crate::generation::number::RandomNumberGenerator;
+use crate::simulation::character::separation::separate;
+
+/// Simulates the aging of [`Characters`](Character).
+/// They can grow up or die every month.
+/// The yearly mortality of their life stage is converted into a monthly one,
+/// so even life stages shorter than a year have a chance to die.
+///
+/// Dead characters are archived together at the end, so they are skipped by all simulation steps.
+/// Their relationships with their mates end, so the widowed mates can find new ones.
+///
+/// Members of races with a [`GenderChange`](crate::data::character::race::gender::GenderOption::GenderChange) change their gender with their life stage.
+/// Afterwards they separate from mates, whose gender isn't allowed by the [`PairBonding`] of their race.
+pub fn simulate_aging(data: &mut SimulationData, rng: &RandomNumberGenerator) {
+    let mut dead = Vec::new();
+
+    for effect in calculate_aging_effects(data, rng) {
         match effect {
             AgingEffect::ChangeLifeStage(id, stage) => {
                 let character = data
@@ -12,37 +32,102 @@ pub fn simulate_aging(data: &mut SimulationData) {
                     .get_mut(id)
                     .expect("Character growing up doesn't exist!");
                 character.set_life_stage(stage);
+                data.history
+                    .add(data.date, EventType::ChangedLifeStage(id, stage));
+                change_gender(data, id, stage);
             }
-            AgingEffect::Death(id) => {
+            AgingEffect::Death(id, cause) => {
                 let character = data
                     .character_manager
                     .get_mut(id)
-                    .expect("Character dying from old age doesn't exist!");
-                character.set_death_date(data.date);
+                    .expect("Dying character doesn't exist!");
+                character.set_death(data.date, cause);
+                dead.push(id);
+                data.relation_manager.end_relations_of(
+                    id,
+                    Mate,
+                    RelationEnd::new(data.date, Death),
+                );
+                data.history.add(data.date, EventType::Died(id, cause));
             }
         }
     }
+
+    data.character_manager.archive_all(dead);
+}
+
+/// Changes the gender of a [`Character`], if its race requires it for the new life stage.
+fn change_gender(data: &mut SimulationData, id: CharacterId, stage: LifeStageId) {
+    let character = data
+        .character_manager
+        .get_mut(id)
+        .expect("Character changing gender doesn't exist!");
+    let race = data
+        .race_manager
+        .get(character.race_id())
+        .expect("Character's race is unknown!");
+
+    if let Some(gender) = race.gender_option().get_changed_gender(stage) {
+        let bonding = *race.bonding();
+        character.set_gender(gender);
+        data.history
+            .add(data.date, EventType::ChangedGender(id, gender));
+        separate_incompatible_mates(data, id, gender, &bonding);
+    }
+}
+
+/// Separates a [`Character`] from all mates, whose gender isn't allowed together with its new one.
+fn separate_incompatible_mates(
+    data: &mut SimulationData,
+    id: CharacterId,
+    gender: Gender,
+    bonding: &PairBonding,
+) {
+    let incompatible_mates: Vec<CharacterId> = data
+        .relation_manager
+        .get_active_relations_of(id, Mate)
+        .map(|relation| relation.target())
+        .filter(|&mate| {
+            let mate = data
+                .character_manager
+                .get(mate)
+                .expect("Mate doesn't exist!");
+            !bonding.allows_genders(gender, mate.gender())
+        })
+        .collect();
+
+    for mate in incompatible_mates {
+        separate(data, id, mate);
+    }
 }
 
 enum AgingEffect {
     /// The [`Characters`](Character) is old enough for
     /// the next [`LifeStage`](crate::data::character::race::stage::LifeStage).
     ChangeLifeStage(CharacterId, LifeStageId),
-    /// The [`Characters`](crate::data::character::Character) is old enough to die from old age.
-    Death(CharacterId),
+    /// The [`Characters`](crate::data::character::Character) dies.
+    Death(CharacterId, DeathCause),
 }
 
 /// Calculates which [`Characters`](Character) are effected by aging.
-fn calculate_aging_effects(data: &SimulationData) -> Vec<AgingEffect> {
+///
+/// Each random number is used only once, so the index is increased after each usage.
+fn calculate_aging_effects(data: &SimulationData, rng: &RandomNumberGenerator) -> Vec<AgingEffect> {
+    let mut index = 0;
+
     data.character_manager
-        .get_all()
-        .iter()
-        .filter_map(|character| calculate_aging_effect(data, character))
+        .get_active()
+        .filter_map(|character| calculate_aging_effect(data, character, rng, &mut index))
         .collect()
 }
 
 /// Calculates if a [`Character`] is effected by aging.
-fn calculate_aging_effect(data: &SimulationData, character: &Character) -> Option<AgingEffect> {
+fn calculate_aging_effect(
+    data: &SimulationData,
+    character: &Character,
+    rng: &RandomNumberGenerator,
+    index: &mut usize,
+) -> Option<AgingEffect> {
     if character.is_dead() {
         return None;
     }
@@ -65,22 +150,45 @@ fn calculate_aging_effect(data: &SimulationData, character: &Character) -> Optio
             let is_last_stage = new_life_stage.index() == race.stages().len();
 
             return if is_last_stage {
-                Some(AgingEffect::Death(character.id()))
+                Some(AgingEffect::Death(character.id(), DeathCause::OldAge))
             } else {
                 Some(AgingEffect::ChangeLifeStage(character.id(), new_life_stage))
             };
         }
     }
 
+    for mortality in stage.mortality() {
+        let current = *index;
+        *index += 1;
+
+        if mortality.probability().per_month().check(rng, current) {
+            return Some(AgingEffect::Death(character.id(), mortality.cause()));
+        }
+    }
+
     None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::data::character::gender::Gender::{Female, Genderless};
-    use crate::data::character::race::tests::{create_immortal_race, create_mortal_race};
-    use crate::data::time::Duration;
+    use crate::data::character::gender::Gender::{Female, Genderless, Male};
+    use crate::data::character::race::gender::GenderOption::GenderChange;
+    use crate::data::character::race::gender::GenderOption::NoGender;
+    use crate::data::character::race::mortality::Mortality;
+    use crate::data::character::race::stage::LifeStage;
+    use crate::data::character::race::tests::{
+        create_immortal_race, create_mortal_race, MortalRace, TestRace,
+    };
+    use crate::data::character::relation::RelationEndReason::Separation;
+    use crate::data::event::{Event, EventKind};
+    use crate::data::probability::{Probability, MAX_RESOLUTION};
+    use crate::data::time::{Date, Duration, MONTHS_PER_YEAR};
+
+    const RNG: RandomNumberGenerator = RandomNumberGenerator::Mock {
+        values: Vec::new(),
+        default: 1,
+    };
 
     #[test]
     fn mortals_die() {
@@ -88,42 +196,54 @@ mod tests {
         let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
         let id = data.create_character("C", race_id, Female).unwrap();
 
-        simulate_aging(&mut data);
+        simulate_aging(&mut data, &RNG);
 
         // 1.life stage
 
         assert_aging(&data, id, 0, true, 0);
 
         data.date.increase_year();
-        simulate_aging(&mut data);
+        simulate_aging(&mut data, &RNG);
 
         assert_aging(&data, id, 1, true, 0);
 
         // 2.life stage
 
         data.date.increase_year();
-        simulate_aging(&mut data);
+        simulate_aging(&mut data, &RNG);
 
         assert_aging(&data, id, 2, true, 1);
 
         data.date.increase_year();
-        simulate_aging(&mut data);
+        simulate_aging(&mut data, &RNG);
 
         assert_aging(&data, id, 3, true, 1);
 
         // character died
 
         data.date.increase_year();
-        simulate_aging(&mut data);
+        simulate_aging(&mut data, &RNG);
 
         assert_aging(&data, id, 4, false, 1);
+        assert!(data.character_manager.is_archived(id));
 
         // dead characters don't age
 
         data.date.increase_year();
-        simulate_aging(&mut data);
+        simulate_aging(&mut data, &RNG);
 
         assert_aging(&data, id, 4, false, 1);
+
+        assert_eq!(
+            data.history.get_all(),
+            &[
+                Event::new(
+                    Date::new(2),
+                    EventType::ChangedLifeStage(id, LifeStageId::new(1))
+                ),
+                Event::new(Date::new(4), EventType::Died(id, DeathCause::OldAge)),
+            ]
+        );
     }
 
     #[test]
@@ -133,7 +253,7 @@ mod tests {
         let id = data.create_character("C", race_id, Genderless).unwrap();
 
         for i in 0..100 {
-            simulate_aging(&mut data);
+            simulate_aging(&mut data, &RNG);
 
             assert_aging(&data, id, i, true, 0);
 
@@ -149,10 +269,10 @@ mod tests {
         data.character_manager
             .get_mut(id)
             .unwrap()
-            .set_death_date(data.date);
+            .set_death(data.date, DeathCause::Accident);
 
         for _i in 0..10 {
-            simulate_aging(&mut data);
+            simulate_aging(&mut data, &RNG);
 
             assert_aging(&data, id, 0, false, 0);
 
@@ -160,6 +280,188 @@ mod tests {
         }
     }
 
+    /// Fails every check, except of certain events.
+    const NEVER: u64 = MAX_RESOLUTION as u64 - 1;
+
+    #[test]
+    fn mortality_kills() {
+        let mut data = SimulationData::default();
+        let probability = Probability::new(1, 2).unwrap();
+        let mortality = vec![
+            Mortality::new(DeathCause::Disease, probability),
+            Mortality::new(DeathCause::Violence, probability),
+        ];
+        let stage = LifeStage::new("Stage", 0, None, None, mortality).unwrap();
+        let race_id = TestRace {
+            name: "Race",
+            gender_option: NoGender,
+            ..TestRace::with_stages(vec![stage])
+        }
+        .create(&mut data.race_manager);
+        let id0 = data.create_character("C0", race_id, Genderless).unwrap();
+        let id1 = data.create_character("C1", race_id, Genderless).unwrap();
+        let id2 = data.create_character("C2", race_id, Genderless).unwrap();
+        let rng = RandomNumberGenerator::Mock {
+            values: vec![NEVER, NEVER, NEVER, 0, 0],
+            default: NEVER,
+        };
+
+        simulate_aging(&mut data, &rng);
+
+        assert_death(&data, id0, None);
+        assert_death(&data, id1, Some(DeathCause::Violence));
+        assert_death(&data, id2, Some(DeathCause::Disease));
+    }
+
+    #[test]
+    fn mortality_is_checked_monthly() {
+        let mut data = SimulationData::default();
+        let probability = Probability::new(2, 4).unwrap();
+        let mortality = vec![Mortality::new(DeathCause::Disease, probability)];
+        let stage = LifeStage::new("Stage", 0, None, None, mortality).unwrap();
+        let race_id = TestRace {
+            name: "Race",
+            gender_option: NoGender,
+            ..TestRace::with_stages(vec![stage])
+        }
+        .create(&mut data.race_manager);
+        let id = data.create_character("C0", race_id, Genderless).unwrap();
+        // would kill with the yearly probability, but not with the monthly one
+        let survive = RandomNumberGenerator::Mock {
+            values: vec![],
+            default: 4_000_000,
+        };
+        let die = RandomNumberGenerator::Mock {
+            values: vec![],
+            default: 1,
+        };
+
+        for _month in 1..MONTHS_PER_YEAR {
+            data.date.increase_month();
+            simulate_aging(&mut data, &survive);
+
+            assert_death(&data, id, None);
+        }
+
+        data.date.increase_month();
+        data.date.increase_month();
+        simulate_aging(&mut data, &die);
+
+        assert_death(&data, id, Some(DeathCause::Disease));
+        assert_eq!(
+            data.character_manager
+                .get(id)
+                .unwrap()
+                .death()
+                .unwrap()
+                .date(),
+            Date::with_month(1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn sub_year_life_stages() {
+        let mut data = SimulationData::default();
+        let stage0 = LifeStage::new("Larva", 0, Some(Duration::from_months(2)), None, vec![]);
+        let stage1 = LifeStage::new("Adult", 1, Some(Duration::from_months(5)), None, vec![]);
+        let stages = vec![stage0.unwrap(), stage1.unwrap()];
+        let race_id = TestRace {
+            name: "Insect",
+            gender_option: NoGender,
+            ..TestRace::with_stages(stages)
+        }
+        .create(&mut data.race_manager);
+        let id = data.create_character("C0", race_id, Genderless).unwrap();
+
+        for month in 0..7 {
+            simulate_aging(&mut data, &RNG);
+
+            let character = data.character_manager.get(id).unwrap();
+            assert_eq!(
+                character.life_stage(),
+                LifeStageId::new((month > 2) as usize)
+            );
+            assert_eq!(character.is_alive(), month < 6);
+
+            data.date.increase_month();
+        }
+    }
+
+    #[test]
+    fn gender_changes_with_life_stage() {
+        let mut data = SimulationData::default();
+        let gender_option = GenderChange {
+            initial: Male,
+            changed: Female,
+            stage: 1,
+        };
+        let race_id = MortalRace {
+            gender_option,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id = data.create_character("C0", race_id, Male).unwrap();
+
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+
+        assert_eq!(data.character_manager.get(id).unwrap().gender(), Male);
+
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+
+        assert_eq!(data.character_manager.get(id).unwrap().gender(), Female);
+        assert_eq!(
+            data.history.get_events_of_kind(EventKind::ChangedGender),
+            vec![&Event::new(
+                Date::new(2),
+                EventType::ChangedGender(id, Female)
+            )]
+        );
+    }
+
+    #[test]
+    fn gender_change_ends_incompatible_bonds() {
+        let mut data = SimulationData::default();
+        let gender_option = GenderChange {
+            initial: Male,
+            changed: Female,
+            stage: 1,
+        };
+        let race_id = MortalRace {
+            gender_option,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let female = data.create_character("F", race_id, Female).unwrap();
+        let male = data.create_character("M", race_id, Male).unwrap();
+        data.relation_manager
+            .add_relation_between(female, male, Mate, data.date);
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+
+        assert!(!data.relation_manager.has_active_relation(female, Mate));
+        assert!(!data.relation_manager.has_active_relation(male, Mate));
+        assert_eq!(
+            data.relation_manager.get_relations_of(male)[0].end(),
+            Some(&RelationEnd::new(Date::new(2), Separation))
+        );
+        assert_eq!(
+            data.history.get_events_of_kind(EventKind::Separated),
+            vec![&Event::new(
+                Date::new(2),
+                EventType::Separated(male, female)
+            )]
+        );
+    }
+
+    fn assert_death(data: &SimulationData, id: CharacterId, cause: Option<DeathCause>) {
+        let character = data.character_manager.get(id).unwrap();
+        assert_eq!(character.death().map(|death| death.cause()), cause);
+    }
+
     fn assert_aging(
         data: &SimulationData,
         id: CharacterId,
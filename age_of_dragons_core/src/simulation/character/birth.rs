@@ -0,0 +1,627 @@
+use crate::data::character::gender::Gender;
+use crate::data::character::race::RaceId;
+use crate::data::character::relation::CharacterRelationType::{Child, Mate};
+use crate::data::character::{Character, CharacterId};
+use crate::data::event::EventType;
+use crate::data::name::Name;
+use crate::data::time::{Date, Duration};
+use crate::data::SimulationData;
+use crate::generation::name::generate_surname;
+use crate::generation::number::RandomNumberGenerator;
+use crate::simulation::character::separation::separate;
+use anyhow::{Context, Result};
+
+/// Simulates [`characters`](Character) with a mate giving birth to offspring.
+/// Genderless characters reproduce asexually without a mate.
+///
+/// The names of the newborns use their own [`RandomNumberGenerator`],
+/// so changing the names of a race doesn't change the rest of the history.
+///
+/// Mates of races without pair-bonding separate afterwards.
+pub fn simulate_birth(
+    data: &mut SimulationData,
+    rng: &RandomNumberGenerator,
+    name_rng: &RandomNumberGenerator,
+) -> Result<()> {
+    let mut name_index = 0;
+
+    for birth in calculate_births(data, rng) {
+        for &gender in &birth.genders {
+            let (name, surname) = generate_names(data, &birth, name_rng, &mut name_index)?;
+            let child = data
+                .create_character(name, birth.race_id, gender)
+                .expect("Failed to create newborn character!");
+            data.character_manager
+                .get_mut(child)
+                .expect("Newborn character doesn't exist!")
+                .set_surname(surname);
+            data.relation_manager
+                .add_relation_between(birth.parent, child, Child, data.date);
+
+            let event_type = match birth.mate {
+                Some(father) => {
+                    data.relation_manager
+                        .add_relation_between(father, child, Child, data.date);
+                    EventType::WasBorn {
+                        character: child,
+                        mother: birth.parent,
+                        father,
+                    }
+                }
+                None => EventType::WasSpawned {
+                    character: child,
+                    parent: birth.parent,
+                },
+            };
+
+            data.history.add(data.date, event_type);
+        }
+
+        data.character_manager
+            .get_mut(birth.parent)
+            .expect("Parent doesn't exist!")
+            .set_last_birth(data.date);
+
+        let race = data
+            .race_manager
+            .get(birth.race_id)
+            .expect("Newborn's race is unknown!");
+
+        if let Some(mate) = birth.mate {
+            if race.bonding().separates_after_birth() {
+                separate(data, birth.parent, mate);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the name of a newborn with the [`NameGenerator`](crate::generation::name::NameGenerator) of its race or numbers it otherwise.
+/// The surname is derived from the parents, where a single parent counts as both father & mother.
+fn generate_names(
+    data: &SimulationData,
+    birth: &Birth,
+    rng: &RandomNumberGenerator,
+    index: &mut usize,
+) -> Result<(String, Option<Name>)> {
+    let race = data
+        .race_manager
+        .get(birth.race_id)
+        .expect("Newborn's race is unknown!");
+    let name = race
+        .name_generator()
+        .map(|generator| generator.generate(rng, index).into())
+        .unwrap_or_else(|| format!("C{}", data.character_manager.len()));
+    let mother = data.character_manager.get(birth.parent);
+    let father = match birth.mate {
+        Some(mate) => data.character_manager.get(mate),
+        None => mother,
+    };
+    let surname = generate_surname(race, father, mother, rng, index)
+        .context("Failed to derive the surname of a newborn")?;
+
+    Ok((name, surname))
+}
+
+/// A mother gives birth to one or more children, or a genderless parent spawns them alone.
+struct Birth {
+    /// The mother or the only parent.
+    parent: CharacterId,
+    /// The father, unless the parent reproduced asexually.
+    mate: Option<CharacterId>,
+    race_id: RaceId,
+    genders: Vec<Gender>,
+}
+
+/// Calculates which [`characters`](Character) give birth this month.
+///
+/// Each random number is used only once, so the index is increased after each usage.
+fn calculate_births(data: &SimulationData, rng: &RandomNumberGenerator) -> Vec<Birth> {
+    let mut index = 0;
+
+    data.character_manager
+        .get_active()
+        .filter_map(|character| calculate_birth(data, character, rng, &mut index))
+        .collect()
+}
+
+/// Calculates if a female or hermaphroditic [`Character`] with a fertile mate or a genderless one gives birth.
+/// The yearly probability depends on the age of the parent & is converted into a monthly one,
+/// so even life stages shorter than a year can reproduce.
+/// Parents can't give birth again during the cooldown of their [`ReproductionOption`](crate::data::character::race::reproduction::ReproductionOption).
+fn calculate_birth(
+    data: &SimulationData,
+    character: &Character,
+    rng: &RandomNumberGenerator,
+    index: &mut usize,
+) -> Option<Birth> {
+    if character.is_dead() {
+        return None;
+    }
+
+    let reproduction = data
+        .race_manager
+        .get_life_stage(character)
+        .reproduction()
+        .as_ref()?;
+    let mate = match character.gender() {
+        Gender::Female => Some(find_fertile_mate(data, character.id(), Gender::Male)?),
+        Gender::Hermaphrodite => Some(find_fertile_mate(
+            data,
+            character.id(),
+            Gender::Hermaphrodite,
+        )?),
+        Gender::Genderless => None,
+        Gender::Male => return None,
+    };
+
+    if is_in_cooldown(character, reproduction.cooldown(), data.date) {
+        return None;
+    }
+
+    let age = character.calculate_age(data.date);
+    let probability_index = next_index(index);
+    let is_pregnant = reproduction
+        .get_probability(age)
+        .map(|probability| probability.per_month().check(rng, probability_index))
+        .unwrap_or(false);
+
+    if !is_pregnant {
+        return None;
+    }
+
+    let race = data
+        .race_manager
+        .get(character.race_id())
+        .expect("Character's race is unknown!");
+    let count = reproduction
+        .offspring_count()
+        .sample(rng, next_index(index));
+    let genders = (0..count)
+        .map(|_i| race.gender_option().generate(rng, next_index(index)))
+        .collect();
+
+    Some(Birth {
+        parent: character.id(),
+        mate,
+        race_id: race.id(),
+        genders,
+    })
+}
+
+/// Did the [`Character`] give birth during the cooldown before the date?
+fn is_in_cooldown(character: &Character, cooldown: Duration, date: Date) -> bool {
+    character
+        .last_birth()
+        .is_some_and(|last_birth| last_birth + cooldown > date)
+}
+
+/// Finds a living current mate of a specific [`Gender`], who is also in a [`LifeStage`](crate::data::character::race::stage::LifeStage) that allows reproduction.
+fn find_fertile_mate(
+    data: &SimulationData,
+    id: CharacterId,
+    gender: Gender,
+) -> Option<CharacterId> {
+    data.relation_manager
+        .get_active_relations_of(id, Mate)
+        .filter_map(|relation| data.character_manager.get(relation.target()))
+        .find(|mate| {
+            mate.is_alive()
+                && mate.gender() == gender
+                && data
+                    .race_manager
+                    .get_life_stage(mate)
+                    .reproduction()
+                    .is_some()
+        })
+        .map(|mate| mate.id())
+}
+
+/// Returns the current index & increases it.
+fn next_index(index: &mut usize) -> usize {
+    let current = *index;
+    *index += 1;
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::death::DeathCause::Disease;
+    use crate::data::character::gender::Gender::{Genderless, Male};
+    use crate::data::character::race::bonding::BondingModel::NoBonding;
+    use crate::data::character::race::bonding::PairBonding;
+    use crate::data::character::race::gender::GenderOption::Hermaphrodite;
+    use crate::data::character::race::gender::GenderOption::NoGender;
+    use crate::data::character::race::mortality::Mortality;
+    use crate::data::character::race::naming::NamingRule;
+    use crate::data::character::race::naming::NamingRule::{Patronymic, Single};
+    use crate::data::character::race::reproduction::count::OffspringCount;
+    use crate::data::character::race::reproduction::fertility::FertilityCurve;
+    use crate::data::character::race::reproduction::ReproductionOption;
+    use crate::data::character::race::stage::LifeStage;
+    use crate::data::character::race::tests::{
+        create_asexual_race, create_mortal_race, MortalRace, TestRace,
+    };
+    use crate::data::character::relation::CharacterRelationType::Parent;
+    use crate::data::event::{Event, EventKind};
+    use crate::data::probability::{Probability, MAX_RESOLUTION};
+    use crate::generation::name::NameGenerator;
+    use crate::simulation::character::aging::simulate_aging;
+    use Gender::Female;
+
+    const SUCCESS: RandomNumberGenerator = RandomNumberGenerator::Mock {
+        values: Vec::new(),
+        default: 0,
+    };
+    const FAILURE: RandomNumberGenerator = RandomNumberGenerator::Mock {
+        values: Vec::new(),
+        default: MAX_RESOLUTION as u64 - 1,
+    };
+
+    #[test]
+    fn mates_give_birth() {
+        let (mut data, id0, id1) = init_adults(true);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 3);
+        let id2 = CharacterId::new(2);
+        let child = data.character_manager.get(id2).unwrap();
+        assert_eq!(child.race_id(), RaceId::new(0));
+        assert_eq!(child.birth_date(), Date::new(2));
+        assert!(child.is_alive());
+
+        let relations = &data.relation_manager;
+        assert_eq!(relations.get_relations_between(id0, id2), vec![Child]);
+        assert_eq!(relations.get_relations_between(id1, id2), vec![Child]);
+        assert_eq!(relations.get_relations_between(id2, id0), vec![Parent]);
+        assert_eq!(relations.get_relations_between(id2, id1), vec![Parent]);
+
+        let mother = data.character_manager.get(id0).unwrap();
+        let father = data.character_manager.get(id1).unwrap();
+        assert_eq!(mother.last_birth(), Some(Date::new(2)));
+        assert_eq!(father.last_birth(), None);
+
+        let event_type = EventType::WasBorn {
+            character: id2,
+            mother: id0,
+            father: id1,
+        };
+        assert_eq!(
+            data.history.get_events_of_kind(EventKind::WasBorn),
+            vec![&Event::new(Date::new(2), event_type)]
+        );
+    }
+
+    #[test]
+    fn newborns_get_generated_names() {
+        let mut data = SimulationData::default();
+        let generator = NameGenerator::new(
+            vec!["Ka".to_string(), "Zu".to_string()],
+            vec![],
+            vec!["ra".to_string()],
+            0,
+        )
+        .unwrap();
+        let race_id = MortalRace {
+            name_generator: Some(generator),
+            naming_rule: Patronymic,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+        let name_rng = RandomNumberGenerator::Mock {
+            values: vec![1],
+            default: 0,
+        };
+
+        simulate_birth(&mut data, &SUCCESS, &name_rng).unwrap();
+
+        let child = data.character_manager.get(CharacterId::new(2)).unwrap();
+        assert_eq!(child.name().to_str(), "Zura");
+        assert_eq!(child.full_name(), "Zura of C1");
+    }
+
+    #[test]
+    fn mates_without_bonding_separate_after_birth() {
+        let mut data = SimulationData::default();
+        let bonding = PairBonding::new(NoBonding, false).unwrap();
+        let race_id = MortalRace {
+            bonding,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 3);
+        assert!(!data.relation_manager.has_active_relation(id0, Mate));
+        assert_eq!(
+            data.history.get_events_of_kind(EventKind::Separated).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn children_of_a_single_parent_are_named_after_it() {
+        for rule in [Patronymic, NamingRule::Matronymic] {
+            let mut data = SimulationData::default();
+            let race_id = create_asexual_race(&mut data.race_manager, rule);
+            data.create_character("S0", race_id, Genderless).unwrap();
+            data.date.increase_year();
+            data.date.increase_year();
+            simulate_aging(&mut data, &SUCCESS);
+
+            simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+            let child = data.character_manager.get(CharacterId::new(1)).unwrap();
+            assert_eq!(child.full_name(), "C1 of S0", "{:?}", rule);
+        }
+    }
+
+    #[test]
+    fn children_of_a_single_parent_inherit_its_clan() {
+        let mut data = SimulationData::default();
+        let race_id = create_asexual_race(&mut data.race_manager, NamingRule::Clan);
+        let parent = data.create_character("S0", race_id, Genderless).unwrap();
+        data.character_manager
+            .get_mut(parent)
+            .unwrap()
+            .set_surname(Some(Name::new("Ooze").unwrap()));
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        let child = data.character_manager.get(CharacterId::new(1)).unwrap();
+        assert_eq!(child.full_name(), "C1 Ooze");
+    }
+
+    #[test]
+    fn genderless_characters_reproduce_alone() {
+        let mut data = SimulationData::default();
+        let race_id = create_asexual_race(&mut data.race_manager, Single);
+        let parent = data.create_character("S0", race_id, Genderless).unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 3);
+        for id in [CharacterId::new(1), CharacterId::new(2)] {
+            let child = data.character_manager.get(id).unwrap();
+            assert_eq!(child.gender(), Genderless);
+            assert_eq!(
+                data.relation_manager.get_relations_between(parent, id),
+                vec![Child]
+            );
+            assert_eq!(data.relation_manager.get_relations_of(id).len(), 1);
+        }
+
+        let events = data.history.get_events_of_kind(EventKind::WasSpawned);
+        let event_type = EventType::WasSpawned {
+            character: CharacterId::new(1),
+            parent,
+        };
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], &Event::new(Date::new(2), event_type));
+    }
+
+    #[test]
+    fn hermaphrodite_mates_both_give_birth() {
+        let mut data = SimulationData::default();
+        let race_id = MortalRace {
+            gender_option: Hermaphrodite,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id0 = data
+            .create_character("C0", race_id, Gender::Hermaphrodite)
+            .unwrap();
+        let id1 = data
+            .create_character("C1", race_id, Gender::Hermaphrodite)
+            .unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        let births = data.history.get_events_of_kind(EventKind::WasBorn);
+        assert_eq!(births.len(), 2);
+        assert_eq!(
+            births[1].event_type(),
+            &EventType::WasBorn {
+                character: CharacterId::new(3),
+                mother: id1,
+                father: id0,
+            }
+        );
+    }
+
+    #[test]
+    fn sub_year_life_stages_give_birth_and_die() {
+        let mut data = SimulationData::default();
+        let probability = Probability::new(1, 2).unwrap();
+        let count = OffspringCount::new_fixed_count(1).unwrap();
+        let reproduction = ReproductionOption::simple(probability, count);
+        let mortality = vec![Mortality::new(Disease, probability)];
+        let larva = LifeStage::new("Larva", 0, Some(Duration::from_months(2)), None, vec![]);
+        let adult = LifeStage::new(
+            "Adult",
+            1,
+            Some(Duration::from_months(9)),
+            Some(reproduction),
+            mortality,
+        );
+        let stages = vec![larva.unwrap(), adult.unwrap()];
+        let race_id = TestRace {
+            name: "Insect",
+            gender_option: NoGender,
+            ..TestRace::with_stages(stages)
+        }
+        .create(&mut data.race_manager);
+        let id = data.create_character("I0", race_id, Genderless).unwrap();
+        for _month in 0..3 {
+            data.date.increase_month();
+            simulate_aging(&mut data, &FAILURE);
+        }
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        let child = data.character_manager.get(CharacterId::new(1)).unwrap();
+        assert_eq!(child.birth_date(), Date::with_month(0, 3).unwrap());
+
+        data.date.increase_month();
+        simulate_aging(&mut data, &SUCCESS);
+
+        let death = data.character_manager.get(id).unwrap().death().unwrap();
+        assert_eq!(death.cause(), Disease);
+        assert_eq!(death.date(), Date::with_month(0, 4).unwrap());
+    }
+
+    #[test]
+    fn failed_probability_check_means_no_birth() {
+        let (mut data, _id0, _id1) = init_adults(true);
+
+        simulate_birth(&mut data, &FAILURE, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 2);
+    }
+
+    #[test]
+    fn no_birth_during_cooldown() {
+        let reproduction = create_reproduction(FertilityCurve::default(), Duration::new(2));
+        let (mut data, id0, _id1) = init_reproducing_adults(reproduction);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+        assert_eq!(data.character_manager.get_all().len(), 3);
+
+        data.date.increase_year();
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+        assert_eq!(data.character_manager.get_all().len(), 3);
+
+        data.date.increase_year();
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+        assert_eq!(data.character_manager.get_all().len(), 4);
+        assert_eq!(
+            data.character_manager
+                .get(CharacterId::new(3))
+                .unwrap()
+                .birth_date(),
+            Date::new(4)
+        );
+        assert!(data.relation_manager.has_active_relation(id0, Mate));
+    }
+
+    #[test]
+    fn no_birth_without_fertility() {
+        let fertility =
+            FertilityCurve::new(vec![(Duration::new(1), 100), (Duration::new(2), 0)]).unwrap();
+        let reproduction = create_reproduction(fertility, Duration::default());
+        let (mut data, _id0, _id1) = init_reproducing_adults(reproduction);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 2);
+    }
+
+    #[test]
+    fn characters_without_mates_dont_give_birth() {
+        let (mut data, _id0, _id1) = init_adults(false);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 2);
+    }
+
+    #[test]
+    fn dead_mates_dont_give_birth() {
+        let (mut data, _id0, id1) = init_adults(true);
+        data.character_manager
+            .get_mut(id1)
+            .unwrap()
+            .set_death(data.date, Disease);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 2);
+    }
+
+    #[test]
+    fn children_dont_give_birth() {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+
+        simulate_birth(&mut data, &SUCCESS, &SUCCESS).unwrap();
+
+        assert_eq!(data.character_manager.get_all().len(), 2);
+    }
+
+    fn create_reproduction(fertility: FertilityCurve, cooldown: Duration) -> ReproductionOption {
+        let probability = Probability::new(1, 5).unwrap();
+        let count = OffspringCount::new_fixed_count(1).unwrap();
+        ReproductionOption::new(probability, count, fertility, cooldown)
+    }
+
+    fn init_reproducing_adults(
+        reproduction: ReproductionOption,
+    ) -> (SimulationData, CharacterId, CharacterId) {
+        let mut data = SimulationData::default();
+        let race_id = MortalRace {
+            reproduction,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+
+        (data, id0, id1)
+    }
+
+    fn init_adults(are_mates: bool) -> (SimulationData, CharacterId, CharacterId) {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &SUCCESS);
+
+        if are_mates {
+            data.relation_manager
+                .add_relation_between(id0, id1, Mate, data.date);
+        }
+
+        (data, id0, id1)
+    }
+}
@@ -0,0 +1,689 @@
+use crate::data::character::gender::Gender;
+use crate::data::character::race::bonding::PairBonding;
+use crate::data::character::race::RaceId;
+use crate::data::character::relation::CharacterRelationType;
+use crate::data::character::{Character, CharacterId};
+use crate::data::event::EventType;
+use crate::data::time::Duration;
+use crate::data::SimulationData;
+use crate::generation::number::RandomNumberGenerator;
+use crate::simulation::character::mate::selection::{MateSelection, ScoredMateSelection};
+use std::collections::{HashMap, HashSet};
+use CharacterRelationType::Mate;
+
+pub mod selection;
+
+/// Simulates [`characters`](Character) finding mates with the default [`MateSelection`].
+pub fn simulate_finding_mate(data: &mut SimulationData, rng: &RandomNumberGenerator) {
+    simulate_finding_mate_with(data, rng, &ScoredMateSelection::default());
+}
+
+/// Simulates [`characters`](Character) finding mates.
+/// They search in a random order & the [`MateSelection`] chooses among the valid candidates.
+pub fn simulate_finding_mate_with(
+    data: &mut SimulationData,
+    rng: &RandomNumberGenerator,
+    selection: &dyn MateSelection,
+) {
+    for (searcher, chosen) in calculate_new_mates(data, rng, selection) {
+        data.relation_manager
+            .add_relation_between(searcher, chosen, Mate, data.date);
+        data.history
+            .add(data.date, EventType::BecameMates(searcher, chosen));
+    }
+}
+
+/// The position of a candidate in the random order. The id breaks ties.
+type OrderKey = (u32, usize);
+
+/// The candidates grouped by race & gender, each group in the random order.
+type Buckets<'a> = HashMap<(RaceId, Gender), Vec<(OrderKey, &'a Character)>>;
+
+/// Each candidate only searches among the candidates after it in the random order,
+/// and only in the buckets of its race with genders allowed by its [`PairBonding`].
+/// The [`MateSelection`] limits how many of them it considers.
+///
+/// Each [`Character`] gets at most one new mate per year, even if it separated or was widowed in between.
+/// Characters that got a new mate less than a year ago in an earlier step aren't candidates,
+/// and candidates are skipped after being paired in this step.
+///
+/// Returns the new pairs as the searching [`Character`] & the chosen one, which can have any genders.
+fn calculate_new_mates(
+    data: &SimulationData,
+    rng: &RandomNumberGenerator,
+    selection: &dyn MateSelection,
+) -> Vec<(CharacterId, CharacterId)> {
+    let candidates = get_candidates_in_random_order(data, rng);
+    let buckets = group_by_race_and_gender(&candidates);
+    let max_candidates = selection.max_candidates();
+    let mut paired = HashSet::new();
+    let mut new_mates = Vec::new();
+
+    for (key, character) in &candidates {
+        if paired.contains(&character.id()) {
+            continue;
+        }
+
+        let bonding = get_bonding(data, character);
+        let later_candidates =
+            get_later_candidates(&buckets, character, bonding, *key, &paired, max_candidates);
+
+        if let Some(other) =
+            find_matching_character(data, character, &later_candidates, rng, selection)
+        {
+            paired.insert(character.id());
+            paired.insert(other);
+            new_mates.push((character.id(), other));
+        }
+    }
+
+    new_mates
+}
+
+/// Returns all [`characters`](Character) that can become mates in a random order.
+/// The order uses the even random indices & the selection the odd ones.
+fn get_candidates_in_random_order<'a>(
+    data: &'a SimulationData,
+    rng: &RandomNumberGenerator,
+) -> Vec<(OrderKey, &'a Character)> {
+    let mut candidates: Vec<(OrderKey, &Character)> = data
+        .character_manager
+        .get_active()
+        .filter(|character| can_become_mate(data, character))
+        .map(|character| {
+            let value = rng.generate(2 * character.id().id(), u32::MAX);
+            ((value, character.id().id()), character)
+        })
+        .collect();
+
+    candidates.sort_by_key(|(key, _character)| *key);
+
+    candidates
+}
+
+fn group_by_race_and_gender<'a>(candidates: &[(OrderKey, &'a Character)]) -> Buckets<'a> {
+    let mut buckets: Buckets = HashMap::new();
+
+    for &(key, character) in candidates {
+        buckets
+            .entry((character.race_id(), character.gender()))
+            .or_default()
+            .push((key, character));
+    }
+
+    buckets
+}
+
+/// Returns up to *max_candidates* unpaired candidates of the same race with allowed genders,
+/// which are after the [`Character`] in the random order.
+fn get_later_candidates<'a>(
+    buckets: &Buckets<'a>,
+    character: &Character,
+    bonding: &PairBonding,
+    key: OrderKey,
+    paired: &HashSet<CharacterId>,
+    max_candidates: usize,
+) -> Vec<&'a Character> {
+    let mut later_candidates: Vec<(OrderKey, &Character)> = Gender::ALL
+        .into_iter()
+        .filter(|&gender| bonding.allows_genders(character.gender(), gender))
+        .filter_map(|gender| buckets.get(&(character.race_id(), gender)))
+        .flat_map(|bucket| {
+            let start = bucket.partition_point(|(other, _character)| *other <= key);
+            bucket[start..]
+                .iter()
+                .filter(|(_key, other)| !paired.contains(&other.id()))
+                .take(max_candidates)
+                .copied()
+        })
+        .collect();
+
+    later_candidates.sort_by_key(|(key, _character)| *key);
+    later_candidates.truncate(max_candidates);
+
+    later_candidates
+        .into_iter()
+        .map(|(_key, character)| character)
+        .collect()
+}
+
+fn find_matching_character(
+    data: &SimulationData,
+    character: &Character,
+    candidates: &[&Character],
+    rng: &RandomNumberGenerator,
+    selection: &dyn MateSelection,
+) -> Option<CharacterId> {
+    let valid_candidates: Vec<&Character> = candidates
+        .iter()
+        .filter(|other| is_valid_match(data, character, other))
+        .copied()
+        .collect();
+
+    if valid_candidates.is_empty() {
+        return None;
+    }
+
+    let index = 2 * character.id().id() + 1;
+    selection.select(data, character, &valid_candidates, rng, index)
+}
+
+fn can_become_mate(data: &SimulationData, character: &Character) -> bool {
+    character.is_alive()
+        && data
+            .race_manager
+            .get_life_stage(character)
+            .reproduction()
+            .is_some()
+        && !had_new_mate_this_year(data, character)
+        && has_room_for_mate(data, character)
+}
+
+/// Did the [`Character`] get a new mate less than a year ago, even if they already separated?
+fn had_new_mate_this_year(data: &SimulationData, character: &Character) -> bool {
+    data.relation_manager
+        .get_relations_of(character.id())
+        .iter()
+        .any(|relation| {
+            relation.relation_type() == Mate && relation.start() + Duration::new(1) > data.date
+        })
+}
+
+/// Has the [`Character`] fewer mates than its [`PairBonding`] allows,
+/// without being a member of the harem of one of them?
+fn has_room_for_mate(data: &SimulationData, character: &Character) -> bool {
+    let max_mates = get_bonding(data, character).max_mates(character.gender());
+    let mates: Vec<CharacterId> = data
+        .relation_manager
+        .get_active_relations_of(character.id(), Mate)
+        .map(|relation| relation.target())
+        .collect();
+
+    mates.len() < max_mates && mates.iter().all(|&mate| count_mates(data, mate) == 1)
+}
+
+/// Characters with mates only bond with characters without, which keeps harems apart.
+fn is_valid_match(data: &SimulationData, character: &Character, candidate: &Character) -> bool {
+    character.race_id() == candidate.race_id()
+        && get_bonding(data, character).allows_genders(character.gender(), candidate.gender())
+        && can_become_mate(data, candidate)
+        && (count_mates(data, character.id()) == 0 || count_mates(data, candidate.id()) == 0)
+}
+
+fn count_mates(data: &SimulationData, id: CharacterId) -> usize {
+    data.relation_manager
+        .get_active_relations_of(id, Mate)
+        .count()
+}
+
+fn get_bonding<'a>(data: &'a SimulationData, character: &Character) -> &'a PairBonding {
+    data.race_manager
+        .get(character.race_id())
+        .expect("Character's race is unknown!")
+        .bonding()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::gender::Gender::{Female, Genderless, Male};
+    use crate::data::character::race::bonding::BondingModel::{
+        Harem, Monogamous, Polyandrous, Polygynous,
+    };
+    use crate::data::character::race::gender::GenderOption::Hermaphrodite;
+    use crate::data::character::race::tests::{
+        create_immortal_race, create_mortal_race, MortalRace,
+    };
+    use crate::data::character::relation::RelationEnd;
+    use crate::data::character::relation::RelationEndReason::Death;
+    use crate::data::event::EventKind;
+    use crate::data::time::{Date, MONTHS_PER_YEAR};
+    use crate::simulation::character::aging::simulate_aging;
+    use crate::simulation::character::separation::separate;
+    use CharacterRelationType::Child;
+
+    const RNG: RandomNumberGenerator = RandomNumberGenerator::Mock {
+        values: Vec::new(),
+        default: 0,
+    };
+
+    #[test]
+    fn two_valid_characters_becoming_mates() {
+        test(Female, Male, vec![Mate]);
+        test(Male, Female, vec![Mate]);
+    }
+
+    #[test]
+    fn same_gender_cant_become_mates() {
+        test(Female, Female, vec![]);
+        test(Male, Male, vec![]);
+    }
+
+    #[test]
+    fn hermaphrodites_become_mates() {
+        let mut data = SimulationData::default();
+        let race_id = MortalRace {
+            gender_option: Hermaphrodite,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id0 = data
+            .create_character("C0", race_id, Gender::Hermaphrodite)
+            .unwrap();
+        let id1 = data
+            .create_character("C1", race_id, Gender::Hermaphrodite)
+            .unwrap();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![Mate]);
+    }
+
+    #[test]
+    fn races_that_dont_reproduce_cant_become_mates() {
+        let mut data = SimulationData::default();
+        let race_id = create_immortal_race(&mut data.race_manager);
+        let id0 = data.create_character("C0", race_id, Genderless).unwrap();
+        let id1 = data.create_character("C1", race_id, Genderless).unwrap();
+
+        for _i in 0..10 {
+            data.date.increase_year();
+            simulate_finding_mate(&mut data, &RNG);
+
+            assert_mate(&data, id0, id1, vec![]);
+        }
+    }
+
+    #[test]
+    fn different_races_cant_become_mates() {
+        let mut data = SimulationData::default();
+        let race_id0 = create_mortal_race(&mut data.race_manager, 1, 3);
+        let race_id1 = create_mortal_race(&mut data.race_manager, 1, 3);
+        let id0 = data.create_character("C0", race_id0, Female).unwrap();
+        let id1 = data.create_character("C1", race_id1, Male).unwrap();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![]);
+    }
+
+    #[test]
+    fn two_characters_cant_becoming_mates_multiple_times() {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![Mate]);
+
+        data.date.increase_year();
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![Mate]);
+    }
+
+    #[test]
+    fn widowed_characters_find_new_mates() {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let old_male = data.create_character("M0", race_id, Male).unwrap();
+        data.date.increase_year();
+        let female = data.create_character("F", race_id, Female).unwrap();
+        let young_male = data.create_character("M1", race_id, Male).unwrap();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, female, old_male, vec![Mate]);
+        assert_mate(&data, female, young_male, vec![]);
+
+        // the old male dies of old age
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, female, old_male, vec![Mate]);
+        assert_mate(&data, female, young_male, vec![Mate]);
+
+        let relations = data.relation_manager.get_relations_of(female);
+        let end = RelationEnd::new(Date::new(4), Death);
+        assert_eq!(relations[0].target(), old_male);
+        assert_eq!(relations[0].end(), Some(&end));
+        assert_eq!(relations[1].target(), young_male);
+        assert_eq!(relations[1].start(), Date::new(4));
+        assert!(relations[1].is_active());
+    }
+
+    #[test]
+    fn siblings_cant_become_mates() {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let parent = data.create_character("P", race_id, Female).unwrap();
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.relation_manager
+            .add_relation_between(parent, id0, Child, data.date);
+        data.relation_manager
+            .add_relation_between(parent, id1, Child, data.date);
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![]);
+    }
+
+    #[test]
+    fn candidates_are_limited_to_other_genders_later_in_order() {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let female = data.create_character("F", race_id, Female).unwrap();
+        let males: Vec<CharacterId> = (0..20)
+            .map(|i| {
+                data.create_character(format!("M{}", i), race_id, Male)
+                    .unwrap()
+            })
+            .collect();
+        let other_female = data.create_character("F2", race_id, Female).unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+
+        let candidates = get_candidates_in_random_order(&data, &RNG);
+        let buckets = group_by_race_and_gender(&candidates);
+
+        let character = data.character_manager.get(female).unwrap();
+        let paired = HashSet::from([males[0]]);
+        let bonding = PairBonding::default();
+        let later: Vec<CharacterId> =
+            get_later_candidates(&buckets, character, &bonding, (0, female.id()), &paired, 10)
+                .iter()
+                .map(|c| c.id())
+                .collect();
+        assert_eq!(later, males[1..=10].to_vec());
+
+        let character = data.character_manager.get(other_female).unwrap();
+        let key = (0, other_female.id());
+        assert!(get_later_candidates(&buckets, character, &bonding, key, &paired, 10).is_empty());
+    }
+
+    #[test]
+    fn large_buckets_are_limited_by_the_selection() {
+        /// Selects the last of at most 3 candidates.
+        struct LastOfThree;
+
+        impl MateSelection for LastOfThree {
+            fn max_candidates(&self) -> usize {
+                3
+            }
+
+            fn select(
+                &self,
+                _data: &SimulationData,
+                _character: &Character,
+                candidates: &[&Character],
+                _rng: &RandomNumberGenerator,
+                _index: usize,
+            ) -> Option<CharacterId> {
+                candidates.last().map(|candidate| candidate.id())
+            }
+        }
+
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let female = data.create_character("F", race_id, Female).unwrap();
+        let males: Vec<CharacterId> = (0..200)
+            .map(|i| {
+                data.create_character(format!("M{}", i), race_id, Male)
+                    .unwrap()
+            })
+            .collect();
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+
+        simulate_finding_mate_with(&mut data, &RNG, &LastOfThree);
+
+        assert_mate(&data, female, males[2], vec![Mate]);
+        assert_eq!(count_mates(&data, female), 1);
+    }
+
+    #[test]
+    fn two_females_dont_share_a_male() {
+        let (data, females, males) = simulate_group(2, 1);
+
+        assert_eq!(count_mates(&data, males[0]), 1);
+        assert_eq!(
+            females
+                .iter()
+                .map(|&id| count_mates(&data, id))
+                .sum::<usize>(),
+            1
+        );
+    }
+
+    #[test]
+    fn two_males_dont_share_a_female() {
+        let (data, females, males) = simulate_group(1, 2);
+
+        assert_eq!(count_mates(&data, females[0]), 1);
+        assert_eq!(
+            males
+                .iter()
+                .map(|&id| count_mates(&data, id))
+                .sum::<usize>(),
+            1
+        );
+    }
+
+    #[test]
+    fn each_character_gets_one_mate() {
+        let (data, females, males) = simulate_group(3, 3);
+
+        for id in females.into_iter().chain(males) {
+            assert_eq!(count_mates(&data, id), 1);
+        }
+
+        let events = data.history.get_events_of_kind(EventKind::BecameMates);
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn separated_characters_wait_a_year_for_a_new_mate() {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let female = data.create_character("F", race_id, Female).unwrap();
+        let male0 = data.create_character("M0", race_id, Male).unwrap();
+        let male1 = data.create_character("M1", race_id, Male).unwrap();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate_with(&mut data, &RNG, &selection::FirstMatchSelection);
+
+        assert_mate(&data, female, male0, vec![Mate]);
+
+        separate(&mut data, female, male0);
+
+        for _month in 1..MONTHS_PER_YEAR {
+            data.date.increase_month();
+            simulate_finding_mate_with(&mut data, &RNG, &selection::FirstMatchSelection);
+
+            assert_eq!(count_mates(&data, female), 0);
+            assert_eq!(count_mates(&data, male1), 0);
+        }
+
+        data.date.increase_month();
+        simulate_finding_mate_with(&mut data, &RNG, &selection::FirstMatchSelection);
+
+        assert_eq!(count_mates(&data, female), 1);
+    }
+
+    #[test]
+    fn same_gender_bonds_if_allowed() {
+        let bonding = PairBonding::new(Monogamous, true).unwrap();
+        let (data, females, _males) = simulate_bonded_group(bonding, 2, 0, 1);
+
+        assert_mate(&data, females[0], females[1], vec![Mate]);
+    }
+
+    #[test]
+    fn polygynous_males_have_multiple_females() {
+        let bonding = PairBonding::new(Polygynous { max_mates: 2 }, false).unwrap();
+        let (data, females, males) = simulate_bonded_group(bonding, 3, 1, 3);
+
+        assert_eq!(count_mates(&data, males[0]), 2);
+        for id in females {
+            assert!(count_mates(&data, id) <= 1);
+        }
+    }
+
+    #[test]
+    fn polyandrous_females_have_multiple_males() {
+        let bonding = PairBonding::new(Polyandrous { max_mates: 3 }, false).unwrap();
+        let (data, females, _males) = simulate_bonded_group(bonding, 1, 3, 3);
+
+        assert_eq!(count_mates(&data, females[0]), 3);
+    }
+
+    #[test]
+    fn leaders_get_one_new_mate_per_year() {
+        let bonding = PairBonding::new(Harem { max_mates: 3 }, false).unwrap();
+        let (mut data, _females, males) = simulate_bonded_group(bonding, 3, 1, 0);
+
+        for _month in 0..12 {
+            simulate_finding_mate_with(&mut data, &RNG, &selection::FirstMatchSelection);
+            data.date.increase_month();
+
+            assert_eq!(count_mates(&data, males[0]), 1);
+        }
+
+        simulate_finding_mate_with(&mut data, &RNG, &selection::FirstMatchSelection);
+
+        assert_eq!(count_mates(&data, males[0]), 2);
+    }
+
+    #[test]
+    fn harem_members_cant_become_mates() {
+        let bonding = PairBonding::new(Harem { max_mates: 3 }, false).unwrap();
+        let (mut data, females, males) = simulate_bonded_group(bonding, 3, 1, 0);
+        let leader = males[0];
+        data.relation_manager
+            .add_relation_between(leader, females[0], Mate, data.date);
+        data.relation_manager
+            .add_relation_between(leader, females[1], Mate, data.date);
+        data.date.increase_year();
+
+        let can_become_mate = |id| can_become_mate(&data, data.character_manager.get(id).unwrap());
+
+        assert!(can_become_mate(leader));
+        assert!(!can_become_mate(females[0]));
+        assert!(!can_become_mate(females[1]));
+        assert!(can_become_mate(females[2]));
+    }
+
+    /// Simulates a year of finding mates for a group of adults.
+    fn simulate_group(
+        females: usize,
+        males: usize,
+    ) -> (SimulationData, Vec<CharacterId>, Vec<CharacterId>) {
+        simulate_bonded_group(PairBonding::default(), females, males, 1)
+    }
+
+    /// Simulates some years of finding mates for a group of adults.
+    fn simulate_bonded_group(
+        bonding: PairBonding,
+        females: usize,
+        males: usize,
+        years: usize,
+    ) -> (SimulationData, Vec<CharacterId>, Vec<CharacterId>) {
+        let mut data = SimulationData::default();
+        let race_id = MortalRace {
+            bonding,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let females: Vec<CharacterId> = (0..females)
+            .map(|i| {
+                data.create_character(format!("F{}", i), race_id, Female)
+                    .unwrap()
+            })
+            .collect();
+        let males: Vec<CharacterId> = (0..males)
+            .map(|i| {
+                data.create_character(format!("M{}", i), race_id, Male)
+                    .unwrap()
+            })
+            .collect();
+
+        data.date.increase_year();
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+
+        for _year in 0..years {
+            simulate_finding_mate_with(&mut data, &RNG, &selection::FirstMatchSelection);
+            data.date.increase_year();
+        }
+
+        (data, females, males)
+    }
+
+    fn test(gender0: Gender, gender1: Gender, result: Vec<CharacterRelationType>) {
+        let mut data = SimulationData::default();
+        let race_id = create_mortal_race(&mut data.race_manager, 1, 3);
+        let id0 = data.create_character("C0", race_id, gender0).unwrap();
+        let id1 = data.create_character("C1", race_id, gender1).unwrap();
+
+        // too young
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![]);
+
+        // too young
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, vec![]);
+
+        // correct age
+        data.date.increase_year();
+        simulate_aging(&mut data, &RNG);
+        simulate_finding_mate(&mut data, &RNG);
+
+        assert_mate(&data, id0, id1, result.clone());
+
+        let events = data.history.get_events_of_kind(EventKind::BecameMates);
+        assert_eq!(events.len(), result.len());
+    }
+
+    fn assert_mate(
+        data: &SimulationData,
+        id0: CharacterId,
+        id1: CharacterId,
+        result: Vec<CharacterRelationType>,
+    ) {
+        assert_eq!(
+            result,
+            data.relation_manager.get_relations_between(id0, id1)
+        );
+        assert_eq!(
+            result,
+            data.relation_manager.get_relations_between(id1, id0)
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::data::character::relation::genealogy::{
+    get_children, get_half_siblings, get_parents, get_siblings,
+};
+use crate::data::character::{Character, CharacterId};
+use crate::data::SimulationData;
+use crate::generation::number::RandomNumberGenerator;
+
+/// The maximum number of candidates a [`Character`] considers, if the [`MateSelection`] doesn't change it.
+pub const DEFAULT_MAX_CANDIDATES: usize = 10;
+
+/// A strategy to select a mate for a [`Character`] from a list of valid candidates.
+pub trait MateSelection {
+    /// The maximum number of candidates a [`Character`] considers, which keeps the mate search near linear.
+    ///
+    /// The candidates are the next unpaired ones after the character in the random order.
+    /// So the possible mates depend on the positions in that order & not on the size of the population,
+    /// and in large populations most members are never considered by a specific character.
+    fn max_candidates(&self) -> usize {
+        DEFAULT_MAX_CANDIDATES
+    }
+
+    /// Selects a mate, if any candidate is acceptable.
+    fn select(
+        &self,
+        data: &SimulationData,
+        character: &Character,
+        candidates: &[&Character],
+        rng: &RandomNumberGenerator,
+        index: usize,
+    ) -> Option<CharacterId>;
+}
+
+/// Selects the first candidate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FirstMatchSelection;
+
+impl MateSelection for FirstMatchSelection {
+    fn select(
+        &self,
+        _data: &SimulationData,
+        _character: &Character,
+        candidates: &[&Character],
+        _rng: &RandomNumberGenerator,
+        _index: usize,
+    ) -> Option<CharacterId> {
+        candidates.first().map(|candidate| candidate.id())
+    }
+}
+
+/// Scores each candidate & randomly selects one, where a higher score is more likely.
+/// Candidates with a similar age get higher scores, while close relatives are never selected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScoredMateSelection {
+    /// Candidates with this age gap or larger get the minimum score.
+    max_age_gap: u32,
+    max_candidates: usize,
+}
+
+impl ScoredMateSelection {
+    pub fn new(max_age_gap: u32, max_candidates: usize) -> Self {
+        Self {
+            max_age_gap,
+            max_candidates,
+        }
+    }
+
+    /// Calculates the score of a candidate. Candidates with a score of 0 are never selected.
+    ///
+    /// ```
+    ///# use age_of_dragons_core::data::character::Character;
+    ///# use age_of_dragons_core::data::character::gender::Gender::*;
+    ///# use age_of_dragons_core::data::character::race::RaceId;
+    ///# use age_of_dragons_core::data::time::Date;
+    ///# use age_of_dragons_core::data::SimulationData;
+    ///# use age_of_dragons_core::simulation::character::mate::selection::ScoredMateSelection;
+    /// let data = SimulationData::default();
+    /// let race_id = RaceId::new(0);
+    /// let character = Character::simple(0, race_id, Female, Date::new(10), None);
+    /// let same_age = Character::simple(1, race_id, Male, Date::new(10), None);
+    /// let older = Character::simple(2, race_id, Male, Date::new(5), None);
+    /// let much_older = Character::simple(3, race_id, Male, Date::new(0), None);
+    /// let selection = ScoredMateSelection::new(10, 5);
+    ///
+    /// assert_eq!(selection.calculate_score(&data, &character, &same_age), 11);
+    /// assert_eq!(selection.calculate_score(&data, &character, &older), 6);
+    /// assert_eq!(selection.calculate_score(&data, &character, &much_older), 1);
+    /// ```
+    pub fn calculate_score(
+        &self,
+        data: &SimulationData,
+        character: &Character,
+        candidate: &Character,
+    ) -> u32 {
+        if is_close_relative(data, character.id(), candidate.id()) {
+            return 0;
+        }
+
+        let age_gap = character
+            .birth_date()
+            .get_duration_since(candidate.birth_date())
+            .year();
+
+        1 + self.max_age_gap.saturating_sub(age_gap)
+    }
+}
+
+impl Default for ScoredMateSelection {
+    fn default() -> Self {
+        Self::new(100, DEFAULT_MAX_CANDIDATES)
+    }
+}
+
+impl MateSelection for ScoredMateSelection {
+    fn max_candidates(&self) -> usize {
+        self.max_candidates
+    }
+
+    fn select(
+        &self,
+        data: &SimulationData,
+        character: &Character,
+        candidates: &[&Character],
+        rng: &RandomNumberGenerator,
+        index: usize,
+    ) -> Option<CharacterId> {
+        let scores: Vec<u32> = candidates
+            .iter()
+            .map(|candidate| self.calculate_score(data, character, candidate))
+            .collect();
+        let total: u32 = scores.iter().sum();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut value = rng.generate(index, total);
+
+        for (candidate, score) in candidates.iter().zip(scores) {
+            if value < score {
+                return Some(candidate.id());
+            }
+
+            value -= score;
+        }
+
+        None
+    }
+}
+
+/// Are the 2 [`characters`](Character) parent & child or (half-)siblings?
+fn is_close_relative(data: &SimulationData, id0: CharacterId, id1: CharacterId) -> bool {
+    let manager = &data.relation_manager;
+
+    get_parents(manager, id0).contains(&id1)
+        || get_children(manager, id0).contains(&id1)
+        || get_siblings(manager, id0).contains(&id1)
+        || get_half_siblings(manager, id0).contains(&id1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::gender::Gender::{Female, Male};
+    use crate::data::character::race::RaceId;
+    use crate::data::character::relation::CharacterRelationType::Child;
+    use crate::data::time::Date;
+
+    #[test]
+    fn test_first_match() {
+        let (data, characters) = init_characters();
+        let candidates = vec![&characters[1], &characters[2]];
+        let rng = create_rng(vec![]);
+
+        assert_eq!(
+            FirstMatchSelection.select(&data, &characters[0], &candidates, &rng, 0),
+            Some(CharacterId::new(1))
+        );
+        assert_eq!(
+            FirstMatchSelection.select(&data, &characters[0], &[], &rng, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scored_selection_is_weighted() {
+        let (data, characters) = init_characters();
+        let candidates = vec![&characters[1], &characters[2]];
+        let rng = create_rng(vec![0, 10, 11, 15]);
+        let selection = ScoredMateSelection::new(10, DEFAULT_MAX_CANDIDATES);
+
+        // the scores are 11 & 5
+        assert_select(&selection, &data, &characters, &candidates, &rng, 0, 1);
+        assert_select(&selection, &data, &characters, &candidates, &rng, 1, 1);
+        assert_select(&selection, &data, &characters, &candidates, &rng, 2, 2);
+        assert_select(&selection, &data, &characters, &candidates, &rng, 3, 2);
+    }
+
+    #[test]
+    fn test_scored_selection_excludes_relatives() {
+        let (mut data, characters) = init_characters();
+        data.relation_manager.add_relation_between(
+            characters[1].id(),
+            characters[0].id(),
+            Child,
+            data.date,
+        );
+        let candidates = vec![&characters[1], &characters[2]];
+        let rng = create_rng(vec![0]);
+        let selection = ScoredMateSelection::new(10, DEFAULT_MAX_CANDIDATES);
+
+        assert_select(&selection, &data, &characters, &candidates, &rng, 0, 2);
+        assert_eq!(
+            selection.select(&data, &characters[0], &candidates[0..1], &rng, 0),
+            None
+        );
+    }
+
+    fn init_characters() -> (SimulationData, Vec<Character>) {
+        let race_id = RaceId::new(0);
+        let characters = vec![
+            Character::simple(0, race_id, Female, Date::new(10), None),
+            Character::simple(1, race_id, Male, Date::new(10), None),
+            Character::simple(2, race_id, Male, Date::new(4), None),
+        ];
+
+        (SimulationData::default(), characters)
+    }
+
+    fn create_rng(values: Vec<u64>) -> RandomNumberGenerator {
+        RandomNumberGenerator::Mock { values, default: 0 }
+    }
+
+    fn assert_select(
+        selection: &ScoredMateSelection,
+        data: &SimulationData,
+        characters: &[Character],
+        candidates: &[&Character],
+        rng: &RandomNumberGenerator,
+        index: usize,
+        result: usize,
+    ) {
+        assert_eq!(
+            selection.select(data, &characters[0], candidates, rng, index),
+            Some(CharacterId::new(result))
+        );
+    }
+}
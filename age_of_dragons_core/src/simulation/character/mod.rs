@@ -1,2 +1,4 @@
 pub mod aging;
+pub mod birth;
 pub mod mate;
+pub mod separation;
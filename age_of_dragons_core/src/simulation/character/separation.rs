@@ -0,0 +1,143 @@
+use crate::data::character::relation::CharacterRelationType::Mate;
+use crate::data::character::relation::RelationEnd;
+use crate::data::character::relation::RelationEndReason::Separation;
+use crate::data::character::CharacterId;
+use crate::data::event::EventType;
+use crate::data::time::Date;
+use crate::data::SimulationData;
+use crate::generation::number::RandomNumberGenerator;
+
+/// Simulates mates separating, if the [`PairBonding`](crate::data::character::race::bonding::PairBonding) of their race allows it.
+/// The yearly probability is only checked in the month they became mates.
+pub fn simulate_separation(data: &mut SimulationData, rng: &RandomNumberGenerator) {
+    for (id0, id1) in calculate_separations(data, rng) {
+        separate(data, id0, id1);
+    }
+}
+
+/// Ends the relation between 2 mates, so both can find new ones.
+pub fn separate(data: &mut SimulationData, id0: CharacterId, id1: CharacterId) {
+    data.relation_manager.end_relation_between(
+        id0,
+        id1,
+        Mate,
+        RelationEnd::new(data.date, Separation),
+    );
+    data.history.add(data.date, EventType::Separated(id0, id1));
+}
+
+/// Calculates which mates separate this month.
+///
+/// Each random number is used only once, so the index is increased after each usage.
+fn calculate_separations(
+    data: &SimulationData,
+    rng: &RandomNumberGenerator,
+) -> Vec<(CharacterId, CharacterId)> {
+    let mut index = 0;
+    let mut separations = Vec::new();
+
+    for character in data.character_manager.get_active() {
+        let race = data
+            .race_manager
+            .get(character.race_id())
+            .expect("Character's race is unknown!");
+        let separation = match race.bonding().separation() {
+            Some(separation) => separation,
+            None => continue,
+        };
+
+        for relation in data
+            .relation_manager
+            .get_active_relations_of(character.id(), Mate)
+        {
+            // each relation is only checked by the character with the lower id
+            if character.id().id() > relation.target().id()
+                || !is_anniversary(data.date, relation.start())
+            {
+                continue;
+            }
+
+            if separation.check(rng, index) {
+                separations.push((character.id(), relation.target()));
+            }
+
+            index += 1;
+        }
+    }
+
+    separations
+}
+
+/// Is it the same month as the start, but in a later year?
+fn is_anniversary(date: Date, start: Date) -> bool {
+    date != start && date.month() == start.month()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::character::gender::Gender::{Female, Male};
+    use crate::data::character::race::bonding::BondingModel::{Monogamous, Serial};
+    use crate::data::character::race::bonding::{BondingModel, PairBonding};
+    use crate::data::character::race::tests::MortalRace;
+    use crate::data::event::{Event, EventKind};
+    use crate::data::probability::Probability;
+
+    const RNG: RandomNumberGenerator = RandomNumberGenerator::Mock {
+        values: Vec::new(),
+        default: 0,
+    };
+
+    #[test]
+    fn serial_mates_separate_on_anniversary() {
+        let separation = Probability::new(1, 2).unwrap();
+        let (mut data, id0, id1) = init_mates(Serial { separation });
+
+        for _month in 0..11 {
+            data.date.increase_month();
+            simulate_separation(&mut data, &RNG);
+
+            assert!(data.relation_manager.has_active_relation(id0, Mate));
+        }
+
+        data.date.increase_month();
+        simulate_separation(&mut data, &RNG);
+
+        assert!(!data.relation_manager.has_active_relation(id0, Mate));
+        assert!(!data.relation_manager.has_active_relation(id1, Mate));
+        assert_eq!(
+            data.history.get_events_of_kind(EventKind::Separated),
+            vec![&Event::new(Date::new(3), EventType::Separated(id0, id1))]
+        );
+    }
+
+    #[test]
+    fn monogamous_mates_never_separate() {
+        let (mut data, id0, _id1) = init_mates(Monogamous);
+
+        for _year in 0..10 {
+            data.date.increase_year();
+            simulate_separation(&mut data, &RNG);
+        }
+
+        assert!(data.relation_manager.has_active_relation(id0, Mate));
+    }
+
+    fn init_mates(model: BondingModel) -> (SimulationData, CharacterId, CharacterId) {
+        let mut data = SimulationData::default();
+        let bonding = PairBonding::new(model, false).unwrap();
+        let race_id = MortalRace {
+            bonding,
+            ..MortalRace::default()
+        }
+        .create(&mut data.race_manager);
+        let id0 = data.create_character("C0", race_id, Female).unwrap();
+        let id1 = data.create_character("C1", race_id, Male).unwrap();
+        data.date.increase_year();
+        data.date.increase_year();
+        data.relation_manager
+            .add_relation_between(id0, id1, Mate, data.date);
+
+        (data, id0, id1)
+    }
+}
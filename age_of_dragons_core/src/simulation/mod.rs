@@ -1,15 +1,117 @@
+use crate::data::time::MONTHS_PER_YEAR;
 use crate::data::SimulationData;
+use crate::generation::number::RandomNumberGenerator;
 use crate::simulation::character::aging::simulate_aging;
+use crate::simulation::character::birth::simulate_birth;
 use crate::simulation::character::mate::simulate_finding_mate;
+use crate::simulation::character::separation::simulate_separation;
+use anyhow::{Context, Result};
 
 pub mod character;
 
-/// Advances the world by a year and simulates everything that happened.
-pub fn simulate_year(data: &mut SimulationData) {
-    println!("Simulate year {}", data.date.year());
+/// Each system uses its own [`RandomNumberGenerator`] to avoid reusing the same random numbers.
+///
+/// The values must never change, because that would change the history of every seed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RandomUsage {
+    Birth = 0,
+    Mate = 1,
+    Aging = 2,
+    Name = 3,
+    Separation = 4,
+}
+
+/// Creates the [`RandomNumberGenerator`] of a system for the current month.
+///
+/// ```
+///# use age_of_dragons_core::data::SimulationData;
+///# use age_of_dragons_core::generation::number::RandomNumberGenerator;
+///# use age_of_dragons_core::simulation::{create_rng, RandomUsage};
+/// let mut data = SimulationData::default();
+/// data.seed = 42;
+/// data.date.increase_year();
+///
+/// assert_eq!(create_rng(&data, RandomUsage::Mate), RandomNumberGenerator::new_hash(42, 12, 1));
+/// ```
+pub fn create_rng(data: &SimulationData, usage: RandomUsage) -> RandomNumberGenerator {
+    RandomNumberGenerator::new_hash(data.seed, data.date.total_months(), usage as u32)
+}
+
+/// Advances the world by a year, which is simulated as a monthly step for each month.
+pub fn simulate_year(data: &mut SimulationData) -> Result<()> {
+    for _month in 0..MONTHS_PER_YEAR {
+        simulate_month(data)?;
+    }
+
+    Ok(())
+}
+
+/// Advances the world by a month and simulates everything that happened.
+pub fn simulate_month(data: &mut SimulationData) -> Result<()> {
+    simulate_separation(data, &create_rng(data, RandomUsage::Separation));
+    simulate_finding_mate(data, &create_rng(data, RandomUsage::Mate));
+    simulate_birth(
+        data,
+        &create_rng(data, RandomUsage::Birth),
+        &create_rng(data, RandomUsage::Name),
+    )
+    .with_context(|| {
+        format!(
+            "Failed to simulate births in {}",
+            data.calendar.format(data.date)
+        )
+    })?;
+    simulate_aging(data, &create_rng(data, RandomUsage::Aging));
+
+    data.date.increase_month();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::save::save_to_string;
+    use crate::data::time::Date;
+    use crate::generation::world::init_simulation;
+    use std::path::Path;
+
+    #[test]
+    fn test_same_seed_gives_same_history() {
+        assert_eq!(simulate(42, 100), simulate(42, 100));
+    }
+
+    #[test]
+    fn test_year_has_monthly_steps() {
+        let mut data = SimulationData::default();
+
+        simulate_month(&mut data).unwrap();
+        assert_eq!(data.date, Date::with_month(0, 1).unwrap());
+
+        simulate_year(&mut data).unwrap();
+        assert_eq!(data.date, Date::with_month(1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_different_seeds_give_different_histories() {
+        assert_ne!(simulate(42, 100), simulate(43, 100));
+    }
+
+    fn simulate(seed: u64, years: u32) -> String {
+        let resources = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let mut data = init_simulation(
+            &resources.join("races"),
+            &resources.join("calendar.ron"),
+            seed,
+        )
+        .unwrap();
 
-    simulate_finding_mate(data);
-    simulate_aging(data);
+        for _i in 0..years {
+            simulate_year(&mut data).unwrap();
+        }
 
-    data.date.increase_year();
+        save_to_string(&data)
+            .unwrap()
+            .replace(&format!("seed: {}", seed), "")
+    }
 }
@@ -0,0 +1,64 @@
+use crate::summary::create_summary;
+use age_of_dragons_core::data::save::{load, save};
+use age_of_dragons_core::generation::world::init_simulation;
+use age_of_dragons_core::simulation::simulate_year;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::fs;
+use std::path::PathBuf;
+
+pub mod summary;
+
+/// Runs the simulation without a user interface.
+#[derive(Parser, Debug)]
+struct Args {
+    /// The number of years to simulate.
+    #[arg(short, long)]
+    years: u32,
+
+    /// Loads the world from this save file instead of generating a new one.
+    #[arg(short, long)]
+    load: Option<PathBuf>,
+
+    /// The directory with the race definitions used to generate a new world.
+    #[arg(short, long, default_value = "resources/races")]
+    races: PathBuf,
+
+    /// The calendar file used to generate a new world.
+    #[arg(short, long, default_value = "resources/calendar.ron")]
+    calendar: PathBuf,
+
+    /// The seed used to generate a new world.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// The save file for the final state of the world.
+    #[arg(short, long, default_value = "world.ron")]
+    output: PathBuf,
+
+    /// The file for the summary of the final state.
+    #[arg(short, long, default_value = "summary.txt")]
+    summary: PathBuf,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut data = match &args.load {
+        Some(path) => load(path)?,
+        None => init_simulation(&args.races, &args.calendar, args.seed)?,
+    };
+
+    for _year in 0..args.years {
+        simulate_year(&mut data)?;
+    }
+
+    save(&data, &args.output)?;
+
+    let summary = create_summary(&data);
+    fs::write(&args.summary, &summary)
+        .with_context(|| format!("Failed to write summary file {:?}", args.summary))?;
+    print!("{}", summary);
+
+    Ok(())
+}
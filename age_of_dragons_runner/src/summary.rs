@@ -0,0 +1,39 @@
+use age_of_dragons_core::data::SimulationData;
+use std::fmt::Write;
+
+/// Creates a human readable summary of the [`SimulationData`].
+pub fn create_summary(data: &SimulationData) -> String {
+    let characters = data.character_manager.get_all();
+    let alive = characters.iter().filter(|c| c.is_alive()).count();
+    let mut summary = String::new();
+
+    writeln!(summary, "Seed: {}", data.seed).unwrap();
+    writeln!(summary, "Date: {}", data.calendar.format(data.date)).unwrap();
+    writeln!(summary, "Races: {}", data.race_manager.get_all().len()).unwrap();
+    writeln!(summary, "Characters: {}", characters.len()).unwrap();
+    writeln!(summary, "Alive: {}", alive).unwrap();
+    writeln!(summary, "Dead: {}", characters.len() - alive).unwrap();
+
+    for race in data.race_manager.get_all() {
+        let alive = characters
+            .iter()
+            .filter(|c| c.is_alive() && c.race_id() == race.id())
+            .count();
+        writeln!(summary, "Alive {}: {}", race.name().to_str(), alive).unwrap();
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_summary() {
+        assert_eq!(
+            create_summary(&SimulationData::default()),
+            "Seed: 0\nDate: January 0\nRaces: 0\nCharacters: 0\nAlive: 0\nDead: 0\n"
+        );
+    }
+}
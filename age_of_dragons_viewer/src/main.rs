@@ -1,19 +1,21 @@
 #[macro_use]
 extern crate rocket;
 
-use crate::init::init_simulation;
-use age_of_dragons_core::data::character::race::RaceId;
+use age_of_dragons_core::data::character::death::DeathCause;
+use age_of_dragons_core::data::character::race::{Race, RaceId};
+use age_of_dragons_core::data::character::relation::CharacterRelation;
 use age_of_dragons_core::data::character::CharacterId;
+use age_of_dragons_core::data::event::{Event, EventType};
 use age_of_dragons_core::data::SimulationData;
+use age_of_dragons_core::generation::world::init_simulation;
 use age_of_dragons_core::simulation::simulate_year;
 use anyhow::Result;
-use rocket::response::Redirect;
+use rocket::response::{Debug, Redirect};
 use rocket::{routes, State};
 use rocket_dyn_templates::{context, Template};
+use std::path::Path;
 use std::sync::Mutex;
 
-pub mod init;
-
 struct ViewerData {
     data: Mutex<SimulationData>,
 }
@@ -26,16 +28,16 @@ fn home(data: &State<ViewerData>) -> Template {
         context! {
             races: data.race_manager.get_all().len(),
             characters: data.character_manager.get_all().len(),
-            year: data.date.year(),
+            date: data.calendar.format(data.date),
         },
     )
 }
 
 #[get("/simulate")]
-fn simulate(data: &State<ViewerData>) -> Redirect {
+fn simulate(data: &State<ViewerData>) -> Result<Redirect, Debug<anyhow::Error>> {
     let mut data = data.data.lock().expect("lock shared data");
-    simulate_year(&mut data);
-    Redirect::to(uri!(home()))
+    simulate_year(&mut data)?;
+    Ok(Redirect::to(uri!(home())))
 }
 
 #[get("/character")]
@@ -48,11 +50,11 @@ fn characters(data: &State<ViewerData>) -> Template {
         .iter()
         .filter(|&c| c.is_alive())
         .count();
-    let characters: Vec<(usize, &str)> = data
+    let characters: Vec<(usize, String)> = data
         .character_manager
         .get_all()
         .iter()
-        .map(|c| (c.id().id(), c.name().to_str()))
+        .map(|c| (c.id().id(), c.full_name()))
         .collect();
 
     Template::render(
@@ -82,37 +84,121 @@ fn character(data: &State<ViewerData>, id: usize) -> Option<Template> {
             Template::render(
                 "character",
                 context! {
-                    name: character.name().to_str(),
+                    name: character.full_name(),
                     id: id,
                     race: race.name().to_str(),
                     race_id: race.id().id(),
                     stage: stage,
                     gender: format!("{:?}", character.gender()),
-                    birth_date: character.birth_date().year(),
+                    birth_date: data.calendar.format(character.birth_date()),
                     age: character.calculate_age(data.date).year(),
                     relations: visualize_relations(&data, character.id()),
+                    events: visualize_events(&data, race, character.id()),
                 },
             )
         })
 }
 
-fn visualize_relations(data: &SimulationData, id: CharacterId) -> Vec<(usize, &str, String)> {
+fn visualize_relations(data: &SimulationData, id: CharacterId) -> Vec<(usize, String, String)> {
     data.relation_manager
         .get_relations_of(id)
         .iter()
         .map(|relation| {
             (
                 relation.target().id(),
-                data.character_manager
-                    .get(relation.target())
-                    .map(|other| other.name().to_str())
-                    .unwrap_or("Unknown"),
-                format!("{:?}", relation.relation_type()),
+                get_character_name(data, relation.target()),
+                visualize_relation_type(data, relation),
             )
         })
         .collect()
 }
 
+fn visualize_relation_type(data: &SimulationData, relation: &CharacterRelation) -> String {
+    match relation.end() {
+        None => format!("{:?}", relation.relation_type()),
+        Some(end) => format!(
+            "{:?} (until {})",
+            relation.relation_type(),
+            data.calendar.format(end.date())
+        ),
+    }
+}
+
+/// Describes an [`Event`] from the point of view of a character & optionally links another one.
+type EventDescription = (String, String, Option<(usize, String)>);
+
+fn visualize_events(data: &SimulationData, race: &Race, id: CharacterId) -> Vec<EventDescription> {
+    data.history
+        .get_events_of(id)
+        .into_iter()
+        .map(|event| visualize_event(data, race, id, event))
+        .collect()
+}
+
+fn visualize_event(
+    data: &SimulationData,
+    race: &Race,
+    id: CharacterId,
+    event: &Event,
+) -> EventDescription {
+    let (text, other) = match event.event_type() {
+        EventType::BecameMates(id0, id1) => {
+            let mate = if *id0 == id { *id1 } else { *id0 };
+            ("Became the mate of".to_string(), Some(mate))
+        }
+        EventType::ChangedGender(_, gender) => (format!("Became {:?}", gender), None),
+        EventType::ChangedLifeStage(_, stage) => {
+            let stage = race
+                .stages()
+                .get(stage.index())
+                .map(|stage| stage.name().to_str())
+                .unwrap_or("Unknown");
+            (format!("Reached the life stage {}", stage), None)
+        }
+        EventType::Died(_, cause) => (visualize_death_cause(*cause).to_string(), None),
+        EventType::Separated(id0, id1) => {
+            let mate = if *id0 == id { *id1 } else { *id0 };
+            ("Separated from".to_string(), Some(mate))
+        }
+        EventType::WasBorn { character, .. } => {
+            if *character == id {
+                ("Was born".to_string(), None)
+            } else {
+                ("Became the parent of".to_string(), Some(*character))
+            }
+        }
+        EventType::WasSpawned { character, .. } => {
+            if *character == id {
+                ("Was spawned".to_string(), None)
+            } else {
+                ("Spawned".to_string(), Some(*character))
+            }
+        }
+    };
+
+    (
+        data.calendar.format(event.date()),
+        text,
+        other.map(|other| (other.id(), get_character_name(data, other))),
+    )
+}
+
+fn visualize_death_cause(cause: DeathCause) -> &'static str {
+    match cause {
+        DeathCause::OldAge => "Died of old age",
+        DeathCause::Disease => "Died of a disease",
+        DeathCause::Accident => "Died in an accident",
+        DeathCause::Violence => "Died by violence",
+    }
+}
+
+fn get_character_name(data: &SimulationData, id: CharacterId) -> String {
+    data.character_manager
+        .get(id)
+        .map(|other| other.full_name())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
 #[get("/race")]
 fn races(data: &State<ViewerData>) -> Template {
     let data = data.data.lock().expect("lock shared data");
@@ -155,9 +241,15 @@ fn race(data: &State<ViewerData>, id: usize) -> Option<Template> {
 
 #[rocket::main]
 async fn main() -> Result<()> {
+    let data = init_simulation(
+        Path::new("resources/races"),
+        Path::new("resources/calendar.ron"),
+        0,
+    )?;
+
     if let Err(e) = rocket::build()
         .manage(ViewerData {
-            data: Mutex::new(init_simulation()),
+            data: Mutex::new(data),
         })
         .mount(
             "/",